@@ -0,0 +1,140 @@
+//! Solves days from their real inputs and compares the answers with the ones recorded
+//! in each day's `answers.toml`, so a refactor that changes an answer is caught.
+
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use get_input::{read_legacy_input, Answer, Cache, Part, PuzzleId, PuzzleInput, RecordedAnswers};
+
+use crate::registry::Day;
+
+/// What checking one day found.
+pub enum DayCheck {
+    /// Every recorded part was solved again; see `PartCheck::drifted`.
+    Solved(Vec<PartCheck>),
+    /// Neither the cache nor the day's directory has the input, so nothing ran.
+    NotCached,
+    /// The day has no recorded answers at this path.
+    NotRecorded(PathBuf),
+}
+
+pub struct PartCheck {
+    pub part: Part,
+    pub expected: Answer,
+    pub actual: Answer,
+    pub elapsed: Duration,
+}
+
+impl PartCheck {
+    pub fn drifted(&self) -> bool {
+        self.actual != self.expected
+    }
+}
+
+/// Solves the recorded parts of `day` from the cached input, or else from an
+/// `input_day_N.txt` left in `workspace/day-NN`, which is read but not imported.
+/// Never downloads or writes to the cache; an unparseable input is an error.
+pub fn check_day(
+    day: &Day,
+    id: PuzzleId,
+    cache: &Cache,
+    answers: &Path,
+    workspace: &Path,
+) -> Result<DayCheck, Box<dyn Error>> {
+    let Some(recorded) = RecordedAnswers::load(answers)? else {
+        return Ok(DayCheck::NotRecorded(answers.to_path_buf()));
+    };
+    let input = match cache.read_input(id)? {
+        Some(input) => input,
+        None => match read_legacy_input(id, workspace)? {
+            Some((_, input)) => input,
+            None => return Ok(DayCheck::NotCached),
+        },
+    };
+
+    let parts: Vec<Part> = [Part::One, Part::Two]
+        .into_iter()
+        .filter(|&part| recorded.expected(part).is_some())
+        .collect();
+    let run = (day.run)(&PuzzleInput::from(input), &parts)?;
+    let checks = run
+        .parts
+        .into_iter()
+        .map(|(part, actual, elapsed)| PartCheck {
+            part,
+            expected: recorded
+                .expected(part)
+                .expect("only recorded parts are run")
+                .clone(),
+            actual,
+            elapsed,
+        })
+        .collect();
+    Ok(DayCheck::Solved(checks))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+    use std::fs;
+
+    const EXAMPLE: &str = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+
+    fn check(cache: &Path, answers: &str, workspace: &Path) -> DayCheck {
+        let path = cache.join("answers.toml");
+        fs::write(&path, answers).unwrap();
+        let id = PuzzleId::new(2024, 1).unwrap();
+        check_day(
+            registry::find(1).unwrap(),
+            id,
+            &Cache::new(cache),
+            &path,
+            workspace,
+        )
+        .unwrap()
+    }
+
+    fn drifted(check: &DayCheck) -> Vec<Part> {
+        match check {
+            DayCheck::Solved(parts) => parts
+                .iter()
+                .filter(|part| part.drifted())
+                .map(|part| part.part)
+                .collect(),
+            _ => panic!("the day was not solved"),
+        }
+    }
+
+    #[test]
+    fn wrong_recorded_answers_are_reported_as_drift() {
+        let dir = tempfile::tempdir().unwrap();
+        let id = PuzzleId::new(2024, 1).unwrap();
+        Cache::new(dir.path()).write_input(id, EXAMPLE).unwrap();
+
+        let matching = check(dir.path(), "part1 = 11\npart2 = 31\n", dir.path());
+        assert!(drifted(&matching).is_empty());
+
+        let wrong = check(dir.path(), "part1 = 11\npart2 = 32\n", dir.path());
+        assert_eq!(drifted(&wrong), [Part::Two]);
+    }
+
+    #[test]
+    fn inputs_left_in_day_directories_are_used_and_missing_ones_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        let workspace = tempfile::tempdir().unwrap();
+        let answers = "part1 = 11\n";
+        assert!(matches!(
+            check(dir.path(), answers, workspace.path()),
+            DayCheck::NotCached
+        ));
+
+        fs::create_dir(workspace.path().join("day-01")).unwrap();
+        fs::write(workspace.path().join("day-01/input_day_1.txt"), EXAMPLE).unwrap();
+        let found = check(dir.path(), answers, workspace.path());
+        assert!(drifted(&found).is_empty());
+        let id = PuzzleId::new(2024, 1).unwrap();
+        assert!(!Cache::new(dir.path()).has_input(id));
+    }
+}
@@ -0,0 +1,236 @@
+mod check;
+mod registry;
+
+use std::env;
+use std::fs;
+use std::io::{self, Read};
+use std::process::ExitCode;
+
+use get_input::{
+    init_logging, Cache, Ledger, LevelFilter, Part, PuzzleId, PuzzleInput, RecordedAnswers,
+    ANSWERS_FILE, DEFAULT_YEAR,
+};
+
+use check::DayCheck;
+use registry::{Day, DAYS};
+
+const USAGE: &str = "Usage: aoc [-q | -v | -vv] <command> [options]
+
+Commands:
+  run                        Solve days and print the answers with their timings
+  check [--day N] [--year Y]
+                             Solve days from their cached inputs (or an input_day_N.txt
+                             left in day-NN) and fail if an answer differs from the
+                             day's answers.toml; days without an input are skipped
+  record --day N [--year Y]  Write the answers the submission ledger shows were
+                             accepted to the day's answers.toml
+
+  -q, -v, -vv                Log only warnings, or also debug or trace output
+                             (AOC_LOG=<level> overrides)
+
+Options for run:
+  --day N                    Run one day; every registered day by default
+  --part 1|2                 Run one part; both by default
+  --year Y                   The year to solve; only 2024 is solved so far
+  --input PATH               Read the input from PATH, or from stdin if PATH is -,
+                             instead of fetching it (needs --day)";
+
+fn main() -> ExitCode {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let verbosity = match args.first().map(String::as_str) {
+        Some("-q") => Some(LevelFilter::Warn),
+        Some("-v") => Some(LevelFilter::Debug),
+        Some("-vv") => Some(LevelFilter::Trace),
+        _ => None,
+    };
+    if verbosity.is_some() {
+        args.remove(0);
+    }
+    init_logging(verbosity.unwrap_or(LevelFilter::Info));
+
+    let (command, options) = match args.split_first() {
+        Some((command, options)) => (command.as_str(), options),
+        None => {
+            println!("{}", USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let result = match command {
+        "run" => run(options),
+        "check" => check(options),
+        "record" => record(options),
+        _ => {
+            println!("{}", USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("aoc {}: {}", command, err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(options: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    let year = year_option(options)?;
+    let parts = match option_value(options, "--part") {
+        Some("1") => vec![Part::One],
+        Some("2") => vec![Part::Two],
+        Some(part) => return Err(format!("invalid part {:?}: must be 1 or 2", part).into()),
+        None => vec![Part::One, Part::Two],
+    };
+    let days = selected_days(options, year)?;
+
+    let input_path = option_value(options, "--input");
+    if input_path.is_some() && days.len() != 1 {
+        return Err("--input needs --day".into());
+    }
+
+    for day in days {
+        let id = PuzzleId::new(year, day.day)?;
+        let input = match input_path {
+            Some("-") => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                PuzzleInput::from(input)
+            }
+            Some(path) => PuzzleInput::from(
+                fs::read_to_string(path).map_err(|err| format!("{}: {}", path, err))?,
+            ),
+            None => get_input::fetch_input_for(id)?,
+        };
+
+        let run = (day.run)(&input, &parts).map_err(|err| format!("{}: {}", id, err))?;
+        println!("{} parse: ({:.2?})", id, run.parse);
+        for (part, answer, elapsed) in run.parts {
+            println!("{} {}: {} ({:.2?})", id, part, answer, elapsed);
+        }
+    }
+    Ok(())
+}
+
+/// Solves every selected day from its cached input, never downloading, and compares
+/// each part with the answer recorded in the day's `answers.toml`. Days whose input
+/// is not available are skipped; drift and unreadable inputs fail the check.
+fn check(options: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    let year = year_option(options)?;
+    let cache = Cache::from_env();
+    let mut checked = 0;
+    let mut skipped = 0;
+    let mut failures = Vec::new();
+
+    for day in selected_days(options, year)? {
+        let id = PuzzleId::new(year, day.day)?;
+        let answers = day.dir().join(ANSWERS_FILE);
+        match check::check_day(day, id, &cache, &answers, registry::workspace_dir()) {
+            Ok(DayCheck::Solved(parts)) => {
+                for part in parts {
+                    checked += 1;
+                    if part.drifted() {
+                        println!(
+                            "{} {}: {} DRIFTED, expected {} ({:.2?})",
+                            id, part.part, part.actual, part.expected, part.elapsed
+                        );
+                        failures.push(format!(
+                            "{} {}: expected {}, got {}",
+                            id, part.part, part.expected, part.actual
+                        ));
+                    } else {
+                        println!(
+                            "{} {}: {} ok ({:.2?})",
+                            id, part.part, part.actual, part.elapsed
+                        );
+                    }
+                }
+            }
+            Ok(DayCheck::NotCached) => {
+                println!(
+                    "{}: skipped, the input is not cached (run `aoc-input prefetch`)",
+                    id
+                );
+                skipped += 1;
+            }
+            Ok(DayCheck::NotRecorded(path)) => {
+                failures.push(format!("{}: nothing recorded in {}", id, path.display()))
+            }
+            Err(err) => failures.push(format!("{}: {}", id, err)),
+        }
+    }
+
+    println!("{} answers checked, {} days skipped", checked, skipped);
+    if failures.is_empty() {
+        return Ok(());
+    }
+    for failure in &failures {
+        eprintln!("FAILED {}", failure);
+    }
+    Err(format!("{} of the checks failed", failures.len()).into())
+}
+
+/// Copies the answers the submission ledger shows were judged correct into the
+/// day's `answers.toml`, keeping any part the ledger has nothing for.
+fn record(options: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    let year = year_option(options)?;
+    let day = option_value(options, "--day").ok_or("--day is required")?;
+    let id = PuzzleId::parse_day(year, day)?;
+    let day = registry::find(id.day()).ok_or_else(|| format!("{} is not solved yet", id))?;
+
+    let ledger = Ledger::load(Cache::from_env().ledger_path(id))?;
+    let accepted = RecordedAnswers::from_ledger(&ledger);
+    if accepted.is_empty() {
+        return Err(format!("{} has no correct answers yet", ledger.path().display()).into());
+    }
+
+    let path = day.dir().join(ANSWERS_FILE);
+    let mut recorded = RecordedAnswers::load(&path)?.unwrap_or_default();
+    for part in [Part::One, Part::Two] {
+        if let Some(answer) = accepted.expected(part) {
+            println!("{} {}: {}", id, part, answer);
+            recorded.record(part, answer.clone());
+        }
+    }
+    recorded.save(&path)?;
+    println!("Recorded in {}", path.display());
+    Ok(())
+}
+
+/// The `--year` asked for. The solutions and their `answers.toml` files are for
+/// `DEFAULT_YEAR`, so any other year is refused rather than checked against them.
+fn year_option(options: &[String]) -> Result<u16, Box<dyn std::error::Error>> {
+    let year = match option_value(options, "--year") {
+        Some(year) => year
+            .parse()
+            .map_err(|_| format!("invalid year {:?}", year))?,
+        None => DEFAULT_YEAR,
+    };
+    if year != DEFAULT_YEAR {
+        return Err(format!("only {} is solved in this workspace", DEFAULT_YEAR).into());
+    }
+    Ok(year)
+}
+
+/// The registered day picked with `--day`, or every registered day.
+fn selected_days(
+    options: &[String],
+    year: u16,
+) -> Result<Vec<&'static Day>, Box<dyn std::error::Error>> {
+    Ok(match option_value(options, "--day") {
+        Some(day) => {
+            let id = PuzzleId::parse_day(year, day)?;
+            vec![registry::find(id.day()).ok_or_else(|| format!("{} is not solved yet", id))?]
+        }
+        None => DAYS.iter().collect(),
+    })
+}
+
+fn option_value<'a>(options: &'a [String], name: &str) -> Option<&'a str> {
+    options
+        .iter()
+        .position(|option| option == name)
+        .and_then(|index| options.get(index + 1))
+        .map(String::as_str)
+}
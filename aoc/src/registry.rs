@@ -0,0 +1,126 @@
+//! Every solved day, linked into the runner. After `setup.sh` creates a day, add it
+//! to `Cargo.toml` and to `DAYS`.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use get_input::{Answer, ParseError, Part, Solution};
+
+/// One day's solution, with its parsed input type erased so every day fits in `DAYS`.
+pub struct Day {
+    pub day: u8,
+    pub run: fn(&str, &[Part]) -> Result<Run, ParseError>,
+}
+
+impl Day {
+    /// The day's crate in the workspace, where its `answers.toml` lives.
+    pub fn dir(&self) -> PathBuf {
+        workspace_dir().join(format!("day-{:02}", self.day))
+    }
+}
+
+/// The workspace root, which holds a `day-NN` directory for every day.
+pub fn workspace_dir() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("the runner is a workspace member")
+}
+
+/// How long the parse took, then each requested part's answer and time.
+#[derive(Debug)]
+pub struct Run {
+    pub parse: Duration,
+    pub parts: Vec<(Part, Answer, Duration)>,
+}
+
+/// Parses `input` once and solves each of `parts` from the same parsed value.
+fn run<S: Solution>(input: &str, parts: &[Part]) -> Result<Run, ParseError> {
+    let start = Instant::now();
+    let parsed = S::parse(input)?;
+    let parse = start.elapsed();
+
+    let parts = parts
+        .iter()
+        .map(|&part| {
+            let start = Instant::now();
+            let answer = match part {
+                Part::One => S::part1(&parsed),
+                Part::Two => S::part2(&parsed),
+            };
+            (part, answer, start.elapsed())
+        })
+        .collect();
+    Ok(Run { parse, parts })
+}
+
+macro_rules! days {
+    ($($day:literal => $krate:ident::$solution:ident),* $(,)?) => {
+        &[$(Day {
+            day: $day,
+            run: run::<$krate::$solution>,
+        }),*]
+    };
+}
+
+/// In day order.
+pub const DAYS: &[Day] = days![
+    1 => day_01::Day01,
+    2 => day_02::Day02,
+    3 => day_03::Day03,
+    4 => day_04::Day04,
+    5 => day_05::Day05,
+    6 => day_06::Day06,
+    7 => day_07::Day07,
+    8 => day_08::Day08,
+    9 => day_09::Day09,
+];
+
+pub fn find(day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|entry| entry.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use get_input::{RecordedAnswers, ANSWERS_FILE};
+
+    #[test]
+    fn days_are_in_order_and_unique() {
+        assert!(DAYS.windows(2).all(|pair| pair[0].day < pair[1].day));
+        assert_eq!(find(5).map(|entry| entry.day), Some(5));
+        assert!(find(25).is_none());
+    }
+
+    #[test]
+    fn runs_parse_once_and_only_the_requested_parts() {
+        let day = find(1).unwrap();
+        let run = (day.run)("3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n", &[Part::Two]).unwrap();
+        let answers: Vec<_> = run
+            .parts
+            .iter()
+            .map(|(part, answer, _)| (*part, answer.clone()))
+            .collect();
+        assert_eq!(answers, [(Part::Two, Answer::from(31))]);
+
+        let err = (day.run)("3 4\n5\n", &[Part::One]).unwrap_err();
+        assert_eq!(err.to_string(), "line 2: expected two location ids");
+    }
+
+    #[test]
+    fn every_day_records_both_accepted_answers() {
+        for day in DAYS {
+            let path = day.dir().join(ANSWERS_FILE);
+            let recorded = RecordedAnswers::load(&path)
+                .unwrap()
+                .unwrap_or_else(|| panic!("{} is missing", path.display()));
+            for part in [Part::One, Part::Two] {
+                assert!(
+                    recorded.expected(part).is_some(),
+                    "{} has no {}",
+                    path.display(),
+                    part
+                );
+            }
+        }
+    }
+}
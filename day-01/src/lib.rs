@@ -1,70 +1,52 @@
 use std::collections::HashMap;
-pub fn part1(input: &str) -> String {
-  let mut list1 = Vec::new();
-  let mut list2 = Vec::new();
-  for line in input.lines() {
-    let parts: Vec<i32> = line.split_whitespace().filter_map(|s| s.parse().ok()).collect();
-    if parts.len() == 2 {
-      list1.push(parts[0]);
-      list2.push(parts[1]);
-    }
-  }
 
-  list1.sort_unstable();
-  list2.sort_unstable();
+use get_input::{Answer, ParseError, Solution};
 
-  let total_distance = list1.iter().zip(list2.iter()).fold(0, |acc, (a, b)| acc + (a - b).abs());
+pub struct Day01;
 
-  // println!("{:?}", list1);
-  // println!("{:?}", list2);
-  // println!("{:?}", total_distance);
-  total_distance.to_string()
-}
+impl Solution for Day01 {
+  // The left and right location lists
+  type Parsed = (Vec<i32>, Vec<i32>);
 
-pub fn part2(input: &str) -> String {
-  let mut list1 = Vec::new();
-  let mut list2 = Vec::new();
-  let mut occurences = HashMap::new();
-  for line in input.lines() {
-    let parts: Vec<i32> = line.split_whitespace().filter_map(|s| s.parse().ok()).collect();
-    if parts.len() == 2 {
+  fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+    let mut list1 = Vec::new();
+    let mut list2 = Vec::new();
+    for (index, line) in input.lines().enumerate() {
+      let parts: Vec<i32> = line.split_whitespace().map(|s| s.parse()).collect::<Result<_, _>>()
+        .map_err(|err| ParseError::from(err).at_line(index + 1))?;
+      if parts.len() != 2 {
+        return Err(ParseError::new("expected two location ids").at_line(index + 1));
+      }
       list1.push(parts[0]);
       list2.push(parts[1]);
     }
-  }
-  // Count occurrences of each number in list2
-  for num in list2 {
-    *occurences.entry(num).or_insert(0) += 1;
+    Ok((list1, list2))
   }
 
-  let mut score = 0;
-  for num in list1 {
-    let occurences_of_num = occurences.get(&num).unwrap_or(&0);
-    score += num * occurences_of_num;
-  }
-  score.to_string()
-}
+  fn part1((list1, list2): &Self::Parsed) -> Answer {
+    let mut list1 = list1.clone();
+    let mut list2 = list2.clone();
+    list1.sort_unstable();
+    list2.sort_unstable();
 
-#[cfg(test)]
-mod tests {
-  use super::*;
+    let total_distance = list1.iter().zip(list2.iter()).fold(0, |acc, (a, b)| acc + (a - b).abs());
+    total_distance.into()
+  }
 
-  const INPUT: &str = "3   4
-4   3
-2   5
-1   3
-3   9
-3   3";
+  fn part2((list1, list2): &Self::Parsed) -> Answer {
+    let mut occurences = HashMap::new();
+    // Count occurrences of each number in list2
+    for num in list2 {
+      *occurences.entry(num).or_insert(0) += 1;
+    }
 
-  #[test]
-  fn part1_works() {
-      let result = part1(INPUT);
-      assert_eq!(result, "11");
+    let mut score = 0;
+    for num in list1 {
+      let occurences_of_num = occurences.get(num).unwrap_or(&0);
+      score += num * occurences_of_num;
+    }
+    score.into()
   }
+}
 
-  #[test]
-  fn part2_works() {
-      let result = part2(INPUT);
-      assert_eq!(result, "31");
-  }
-}
\ No newline at end of file
+get_input::example_tests!(Day01);
@@ -1,10 +1,12 @@
+use get_input::{Answer, ParseError, Solution};
+
 fn is_safe(report: &[i32]) -> bool {
     let mut increasing = true;
     let mut decreasing = true;
     for i in 0..report.len() - 1 {
       let abs_diff = (report[i] - report[i + 1]).abs();
 
-      if abs_diff < 1 || abs_diff > 3 {
+      if !(1..=3).contains(&abs_diff) {
         return false;
       }
 
@@ -33,46 +35,33 @@ fn is_safe_with_dampener(report: &[i32]) -> bool {
   false
 }
 
-pub fn part1(input: &str) -> String {
-  let output = input.lines()
-  .map(|line| {
-    line.split_whitespace().filter_map(|s| s.parse().ok()).collect::<Vec<i32>>() 
-  }).filter(|report| is_safe(&report))
-  .count();
-  
-  output.to_string()
-}
-
-pub fn part2(input: &str) -> String {
-  let output = input.lines()
-  .map(|line| {
-    line.split_whitespace().filter_map(|s| s.parse().ok()).collect::<Vec<i32>>() 
-  }).filter(|report| is_safe_with_dampener(&report))
-  .count();
-  
-  output.to_string()
-}
+pub struct Day02;
 
-#[cfg(test)]
-mod tests {
-  use super::*;
+impl Solution for Day02 {
+  // One report of levels per line
+  type Parsed = Vec<Vec<i32>>;
 
-  const INPUT: &str = "7 6 4 2 1
-1 2 7 8 9
-9 7 6 2 1
-1 3 2 4 5
-8 6 4 4 1
-1 3 6 7 9";
+  fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+    input.lines().enumerate()
+    .map(|(index, line)| {
+      let report = line.split_whitespace().map(|s| s.parse()).collect::<Result<Vec<i32>, _>>()
+        .map_err(|err| ParseError::from(err).at_line(index + 1))?;
+      if report.is_empty() {
+        return Err(ParseError::new("empty report").at_line(index + 1));
+      }
+      Ok(report)
+    }).collect()
+  }
 
-  #[test]
-  fn part1_works() {
-      let result = part1(INPUT);
-      assert_eq!(result, "2");
+  fn part1(reports: &Self::Parsed) -> Answer {
+    let output = reports.iter().filter(|report| is_safe(report)).count();
+    output.into()
   }
 
-  #[test]
-  fn part2_works() {
-      let result = part2(INPUT);
-      assert_eq!(result, "4");
+  fn part2(reports: &Self::Parsed) -> Answer {
+    let output = reports.iter().filter(|report| is_safe_with_dampener(report)).count();
+    output.into()
   }
-}
\ No newline at end of file
+}
+
+get_input::example_tests!(Day02);
@@ -1,116 +1,96 @@
-pub fn part1(input: &str) -> String {
-    let mut sum = 0;
-    let mut i = 0;
+use get_input::{Answer, ParseError, Solution};
 
-    let chars: Vec<char> = input.chars().collect();
-    while i < chars.len() {
-        if chars[i..].starts_with(&['m', 'u', 'l', '(']) {
-            let mut start = i + 4;
-            let mut num1 = String::new();
-            let mut num2 = String::new();
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    Mul(i32, i32),
+    Do,
+    Dont,
+}
 
-            while start < chars.len() && chars[start].is_digit(10) {
-                num1.push(chars[start]);
-                start += 1;
-            }
+// Reads `mul(X,Y)` from `start`, the index just past `mul(`. Returns the product's
+// operands if it is well formed, and the index to keep scanning from.
+fn read_mul(chars: &[char], mut start: usize) -> (Option<(i32, i32)>, usize) {
+    let mut num1 = String::new();
+    let mut num2 = String::new();
 
-            if start < chars.len() && chars[start] == ',' {
-                start += 1;
-            } else {
-                i = start;
-                continue;
-            }
+    while start < chars.len() && chars[start].is_ascii_digit() {
+        num1.push(chars[start]);
+        start += 1;
+    }
 
-            while start < chars.len() && chars[start].is_digit(10) {
-                num2.push(chars[start]);
-                start += 1;
-            }
+    if start < chars.len() && chars[start] == ',' {
+        start += 1;
+    } else {
+        return (None, start);
+    }
 
-            if start < chars.len() && chars[start] == ')' {
-                if let (Ok(num1), Ok(num2)) = (num1.parse::<i32>(), num2.parse::<i32>()) {
-                    sum += num1 * num2;
-                }
-            }
+    while start < chars.len() && chars[start].is_ascii_digit() {
+        num2.push(chars[start]);
+        start += 1;
+    }
 
-            i = start + 1;
-        } else {
-            i += 1;
+    if start < chars.len() && chars[start] == ')' {
+        if let (Ok(num1), Ok(num2)) = (num1.parse::<i32>(), num2.parse::<i32>()) {
+            return (Some((num1, num2)), start + 1);
         }
     }
-
-    sum.to_string()
+    (None, start + 1)
 }
 
-pub fn part2(input: &str) -> String {
-    let mut sum2 = 0;
-    let chars: Vec<char> = input.chars().collect();
-    let mut i = 0;
-    let mut enabled = true;
+pub struct Day03;
 
-    while i < chars.len() {
-        if chars[i..].starts_with(&['d', 'o', '(', ')']) {
-            enabled = true;
-            i += 4;
-        } else if chars[i..].starts_with(&['d', 'o', 'n', '\'', 't', '(', ')'])
-        {
-            enabled = false;
-            i += 7;
-        } else if chars[i..].starts_with(&['m', 'u', 'l', '(']) {
-            if enabled {
-                let mut start = i + 4;
-                let mut num1 = String::new();
-                let mut num2 = String::new();
+impl Solution for Day03 {
+    // The instructions found in the corrupted memory, in order
+    type Parsed = Vec<Instruction>;
 
-                while start < chars.len() && chars[start].is_digit(10) {
-                    num1.push(chars[start]);
-                    start += 1;
-                }
-
-                if start < chars.len() && chars[start] == ',' {
-                    start += 1;
-                } else {
-                    i = start;
-                    continue;
-                }
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let mut instructions = Vec::new();
+        let chars: Vec<char> = input.chars().collect();
+        let mut i = 0;
 
-                while start < chars.len() && chars[start].is_digit(10) {
-                    num2.push(chars[start]);
-                    start += 1;
-                }
-
-                if start < chars.len() && chars[start] == ')' {
-                    if let (Ok(num1), Ok(num2)) = (num1.parse::<i32>(), num2.parse::<i32>()) {
-                        sum2 += num1 * num2;
-                    }
+        while i < chars.len() {
+            if chars[i..].starts_with(&['d', 'o', '(', ')']) {
+                instructions.push(Instruction::Do);
+                i += 4;
+            } else if chars[i..].starts_with(&['d', 'o', 'n', '\'', 't', '(', ')']) {
+                instructions.push(Instruction::Dont);
+                i += 7;
+            } else if chars[i..].starts_with(&['m', 'u', 'l', '(']) {
+                let (mul, next) = read_mul(&chars, i + 4);
+                if let Some((num1, num2)) = mul {
+                    instructions.push(Instruction::Mul(num1, num2));
                 }
-
-                i = start + 1;
+                i = next;
             } else {
-                i += 4;
+                i += 1;
             }
-        } else {
-            i += 1;
         }
+        Ok(instructions)
     }
-    sum2.to_string()
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
 
-    const INPUT: &str = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
-    const INPUT2: &str = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
-
-    #[test]
-    fn part1_works() {
-        let result = part1(INPUT);
-        assert_eq!(result, "161");
+    fn part1(instructions: &Self::Parsed) -> Answer {
+        let mut sum = 0;
+        for instruction in instructions {
+            if let Instruction::Mul(num1, num2) = instruction {
+                sum += num1 * num2;
+            }
+        }
+        sum.into()
     }
 
-    #[test]
-    fn part2_works() {
-        let result = part2(INPUT2);
-        assert_eq!(result, "48");
+    fn part2(instructions: &Self::Parsed) -> Answer {
+        let mut sum2 = 0;
+        let mut enabled = true;
+        for instruction in instructions {
+            match instruction {
+                Instruction::Do => enabled = true,
+                Instruction::Dont => enabled = false,
+                Instruction::Mul(num1, num2) if enabled => sum2 += num1 * num2,
+                Instruction::Mul(..) => {}
+            }
+        }
+        sum2.into()
     }
 }
+
+get_input::example_tests!(Day03);
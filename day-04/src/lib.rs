@@ -1,4 +1,27 @@
-pub fn part1(input: &str) -> String {
+use get_input::{Answer, ParseError, PuzzleInput, Shape, Solution};
+
+pub struct Day04;
+
+impl Solution for Day04 {
+  // The word search, one row of letters per line
+  type Parsed = Vec<Vec<char>>;
+
+  fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+    // The searches below assume every row is as long as the first
+    let input = PuzzleInput::new(input).expect(Shape::Rectangular)?;
+    Ok(input.grid())
+  }
+
+  fn part1(grid: &Self::Parsed) -> Answer {
+    part1(grid)
+  }
+
+  fn part2(grid: &Self::Parsed) -> Answer {
+    part2(grid)
+  }
+}
+
+fn part1(grid: &[Vec<char>]) -> Answer {
   let directions: [(isize, isize); 8] = [
     (0,1),  //Right
     (0,-1), //Left
@@ -11,9 +34,6 @@ pub fn part1(input: &str) -> String {
   ];
   let word = ['X', 'M', 'A', 'S'];
   let mut count = 0;
-  let grid: Vec<Vec<char>> = input.lines().map(|line| {
-    line.chars().collect::<Vec<char>>()
-  }).collect();
   let rows = grid.len();
   let cols = grid[0].len();
   
@@ -21,14 +41,14 @@ pub fn part1(input: &str) -> String {
     for col in 0..cols {
       for &(dr, dc) in &directions {
         let mut found = true;
-        for i in 0..word.len() {
+        for (i, &letter) in word.iter().enumerate() {
           let nr = row as isize + (dr * i as isize) ;
           let nc = col as isize + (dc * i as isize);
           if nr < 0 || nr >= rows as isize || nc < 0 || nc >= cols as isize {
             found = false;
             break;
           }
-          if grid[nr as usize][nc as usize] != word[i] {
+          if grid[nr as usize][nc as usize] != letter {
             found = false;
             break;
           }
@@ -39,16 +59,12 @@ pub fn part1(input: &str) -> String {
       }
     }
   }
-  count.to_string()
+  count.into()
 }
 
-pub fn part2(input: &str) -> String {
+fn part2(grid: &[Vec<char>]) -> Answer {
   let mut count = 0;
 
-  let grid: Vec<Vec<char>> = input.lines().map(|line| {
-      line.chars().collect::<Vec<char>>()
-  }).collect();
-
   let rows = grid.len();
   let cols = grid[0].len();
 
@@ -69,34 +85,8 @@ pub fn part2(input: &str) -> String {
     }
   }
 
-  count.to_string()
+  count.into()
 }
 
 
-#[cfg(test)]
-mod tests {
-  use super::*;
-
-  const INPUT: &str = "MMMSXXMASM
-MSAMXMSMSA
-AMXSXMAAMM
-MSAMASMSMX
-XMASAMXAMM
-XXAMMXXAMA
-SMSMSASXSS
-SAXAMASAAA
-MAMMMXMMMM
-MXMXAXMASX";
-
-  #[test]
-  fn part1_works() {
-      let result = part1(INPUT);
-      assert_eq!(result, "18");
-  }
-
-  #[test]
-  fn part2_works() {
-      let result = part2(INPUT);
-      assert_eq!(result, "9");
-  }
-}
\ No newline at end of file
+get_input::example_tests!(Day04);
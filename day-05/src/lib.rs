@@ -1,29 +1,36 @@
 use std::collections::{HashMap, HashSet, VecDeque};
 
+use get_input::{Answer, ParseError, PuzzleInput, Solution};
+
+// a rule says the first page must be printed before the second
+type Rule = (i32, i32);
+
 // parse input into rules (a vector of tuples) and updates (a vector of i32 vectors)
-fn parse_input(input: &str) -> (Vec<(i32,i32)>, Vec<Vec<i32>>) {
-  let rules_and_updates: Vec<&str> = input.trim().split("\n\n").collect();
+fn parse_input(input: &str) -> Result<(Vec<Rule>, Vec<Vec<i32>>), ParseError> {
+  // PuzzleInput normalizes CRLF, so the blank line between the sections is always found
+  let input = PuzzleInput::new(input);
+  let rules_and_updates: Vec<&str> = input.blocks().collect();
+  if rules_and_updates.len() != 2 {
+    return Err(ParseError::new("expected rules and updates separated by a blank line"));
+  }
 
-  let rules: Vec<(i32, i32)> = rules_and_updates[0].lines()
+  let rules: Vec<Rule> = rules_and_updates[0].lines()
     .map(|line| {
-      let pages: Vec<i32> = line.split('|').map(|page| page.parse().unwrap()).collect();
-      (pages[0], pages[1])
+      let (page1, page2) = line.split_once('|')
+        .ok_or_else(|| ParseError::new(format!("expected a rule like 47|53, found {:?}", line)))?;
+      Ok((page1.parse()?, page2.parse()?))
     })
-    .collect();
-  println!("Parsed {} rules", rules.len());
-  println!("{:?}", rules);
+    .collect::<Result<_, ParseError>>()?;
 
   let updates: Vec<Vec<i32>> = rules_and_updates[1].lines()
     .map(|line| {
-      line.split(',').map(|page| page.parse().unwrap()).collect()
+      line.split(',').map(|page| page.parse()).collect()
     })
-    .collect();
-  println!("Parsed {} updates", updates.len());
-  println!("{:?}", updates);
-  (rules, updates)
+    .collect::<Result<_, _>>()?;
+  Ok((rules, updates))
 }
 
-fn build_graph(rules: &Vec<(i32, i32)>) -> HashMap<i32, Vec<i32>> {
+fn build_graph(rules: &[Rule]) -> HashMap<i32, Vec<i32>> {
   let mut graph: HashMap<i32, Vec<i32>> = HashMap::new();
   // iterate over the rules and build the graph
   for (page1, page2) in rules {
@@ -32,8 +39,6 @@ fn build_graph(rules: &Vec<(i32, i32)>) -> HashMap<i32, Vec<i32>> {
     // push the neighbor to the vector
     entry.push(*page2);
   }
-  println!("Built graph with {} nodes", graph.len());
-  println!("{:?}", graph);
   graph
 }
 
@@ -54,7 +59,7 @@ fn get_valid_ordering(graph: &HashMap<i32, Vec<i32>>, update: &Vec<i32>) -> Vec<
   for (&page, neighbors) in graph {
     if update_set.contains(&page) {
       // filter neighbors to include only nodes from the update
-      let valid_neighbours: Vec<i32> = neighbors.iter().filter(|&n| update_set.contains(&n)).cloned().collect();
+      let valid_neighbours: Vec<i32> = neighbors.iter().filter(|&n| update_set.contains(n)).cloned().collect();
       // insert the page and its valid neighbours into nodes
       nodes.insert(page, valid_neighbours.clone());
       // increment the number of nodes that must be visited before each node
@@ -71,7 +76,6 @@ fn get_valid_ordering(graph: &HashMap<i32, Vec<i32>>, update: &Vec<i32>) -> Vec<
     .iter()
     .filter_map(|(&page, &value)| if value == 0 {Some(page)} else {None})
     .collect();
-  println!("Starting queue with nodes that do not require any other nodes to be visited first: {:?}", queue);
 
   //initialize a vector to hold the order of nodes
   let mut sorted_order = Vec::new();
@@ -96,92 +100,51 @@ fn get_valid_ordering(graph: &HashMap<i32, Vec<i32>>, update: &Vec<i32>) -> Vec<
       }
     }
   }
-  println!("Sorted order: {:?}", sorted_order);
   sorted_order
 }
 
 
-pub fn part1(input: &str) -> String {
-  let (rules, updates) = parse_input(input);
-  let graph = build_graph(&rules);
-  let mut total = 0;
+pub struct Day05;
 
-  for update in &updates {
-    let valid_order = get_valid_ordering(&graph, update);
-    let valid = valid_order == *update;
-    if valid {
-      let middle_page = update[update.len() / 2];
-      println!("Middle page of update {:?}: {}", update, middle_page);
-      total += middle_page;
-    }
+impl Solution for Day05 {
+  // The ordering rules as a graph of the pages that must come after each page,
+  // and the updates
+  type Parsed = (HashMap<i32, Vec<i32>>, Vec<Vec<i32>>);
+
+  fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+    let (rules, updates) = parse_input(input)?;
+    Ok((build_graph(&rules), updates))
   }
-  
-  println!("Total: {}", total);
-  total.to_string()
-}
 
-pub fn part2(input: &str) -> String {
-  let (rules, updates) = parse_input(input);
-  let graph = build_graph(&rules);
-  let mut total = 0;
-  
-
-  for update in &updates {
-    let valid_order = get_valid_ordering(&graph, update);
-    let valid = valid_order == *update;
-    if !valid {
-      let middle_page = valid_order[valid_order.len() / 2];
-      println!("Middle page of update {:?}: {}", update, middle_page);
-      total += middle_page;
+  fn part1((graph, updates): &Self::Parsed) -> Answer {
+    let mut total = 0;
+
+    for update in updates {
+      let valid_order = get_valid_ordering(graph, update);
+      let valid = valid_order == *update;
+      if valid {
+        let middle_page = update[update.len() / 2];
+        total += middle_page;
+      }
     }
-  }
-  
-  println!("Total: {}", total);
-  total.to_string()
-}
 
-#[cfg(test)]
-mod tests {
-  use super::*;
-
-  const INPUT: &str = "47|53
-97|13
-97|61
-97|47
-75|29
-61|13
-75|53
-29|13
-97|29
-53|29
-61|53
-97|53
-61|29
-47|13
-75|47
-97|75
-47|61
-75|61
-47|29
-75|13
-53|13
-
-75,47,61,53,29
-97,61,53,29,13
-75,29,13
-75,97,47,61,53
-61,13,29
-97,13,75,29,47";
-
-  #[test]
-  fn part1_works() {
-      let result = part1(INPUT);
-      assert_eq!(result, "143");
+    total.into()
   }
 
-  #[test]
-  fn part2_works() {
-      let result = part2(INPUT);
-      assert_eq!(result, "123");
+  fn part2((graph, updates): &Self::Parsed) -> Answer {
+    let mut total = 0;
+
+    for update in updates {
+      let valid_order = get_valid_ordering(graph, update);
+      let valid = valid_order == *update;
+      if !valid {
+        let middle_page = valid_order[valid_order.len() / 2];
+        total += middle_page;
+      }
+    }
+
+    total.into()
   }
-}
\ No newline at end of file
+}
+
+get_input::example_tests!(Day05);
@@ -1,6 +1,12 @@
-use std::{collections::HashSet, time::Instant};
+use std::collections::HashSet;
 
-fn parse_input(input: &str) -> (Vec<Vec<char>>, (usize, usize), (isize, isize)) {
+use get_input::{Answer, ParseError, PuzzleInput, Shape, Solution};
+
+type Position = (usize, usize);
+type Direction = (isize, isize);
+
+fn parse_input(input: &str) -> Result<(Vec<Vec<char>>, Position, Direction), ParseError> {
+    let input = PuzzleInput::new(input).expect(Shape::Rectangular)?;
     let mut map_data = Vec::new();
     let mut guard_pos = (0, 0);
     let mut guard_dir = (0, 0);
@@ -34,8 +40,11 @@ fn parse_input(input: &str) -> (Vec<Vec<char>>, (usize, usize), (isize, isize))
         }
         map_data.push(row);
     }
+    if guard_dir == (0, 0) {
+        return Err(ParseError::new("no guard (^, >, v or <) on the map"));
+    }
     
-    (map_data, guard_pos, guard_dir)
+    Ok((map_data, guard_pos, guard_dir))
 }
 
 fn turn_right(dir: (isize, isize)) -> (isize, isize) {
@@ -50,7 +59,7 @@ fn turn_right(dir: (isize, isize)) -> (isize, isize) {
 
 
 fn simulate_guard_obstructions(
-    grid: &Vec<Vec<char>>, 
+    grid: &[Vec<char>], 
     mut pos: (usize, usize), 
     mut dir: (isize, isize),
     obstruction: (usize, usize)
@@ -82,11 +91,11 @@ fn simulate_guard_obstructions(
           pos = next_pos;
       }
     }
-    return false
+    false
 }
 
 fn simulate_guard(
-    grid: &Vec<Vec<char>>, 
+    grid: &[Vec<char>], 
     mut pos: (usize, usize), 
     mut dir: (isize, isize)
 ) -> (usize, HashSet<(usize, usize)>) {
@@ -123,53 +132,32 @@ fn simulate_guard(
 }
 
 
-pub fn part1(input: &str) -> String {
-  let (map_data, guard_pos, guard_dir) = parse_input(input);
-  let distinct_positions = simulate_guard(&map_data, guard_pos, guard_dir);
-  distinct_positions.0.to_string()
-}
-
-pub fn part2(input: &str) -> String {
-  let start = Instant::now();
-  let mut count = 0;
-  let (map_data, guard_pos, guard_dir) = parse_input(input);
-  let distinct_positions = simulate_guard(&map_data,guard_pos, guard_dir);
-  for obstruction in distinct_positions.1 {
-    let obstructed = simulate_guard_obstructions(&map_data, guard_pos, guard_dir, obstruction);
-    if obstructed {
-      count += 1;
-    }
-  }
-  let duration = start.elapsed();
-  println!("Time elapsed in expensive_function() is: {:?}", duration);
-  count.to_string()
-
-}
+pub struct Day06;
 
-#[cfg(test)]
-mod tests {
-  use super::*;
+impl Solution for Day06 {
+  // The map with the guard replaced by floor, and where the guard starts and faces
+  type Parsed = (Vec<Vec<char>>, Position, Direction);
 
-  const INPUT: &str = "....#.....
-.........#
-..........
-..#.......
-.......#..
-..........
-.#..^.....
-........#.
-#.........
-......#...";
+  fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+    parse_input(input)
+  }
 
-  #[test]
-  fn part1_works() {
-      let result = part1(INPUT);
-      assert_eq!(result, "41");
+  fn part1((map_data, guard_pos, guard_dir): &Self::Parsed) -> Answer {
+    let distinct_positions = simulate_guard(map_data, *guard_pos, *guard_dir);
+    distinct_positions.0.into()
   }
 
-  #[test]
-  fn part2_works() {
-      let result = part2(INPUT);
-      assert_eq!(result, "6");
+  fn part2((map_data, guard_pos, guard_dir): &Self::Parsed) -> Answer {
+    let mut count = 0;
+    let distinct_positions = simulate_guard(map_data, *guard_pos, *guard_dir);
+    for obstruction in distinct_positions.1 {
+      let obstructed = simulate_guard_obstructions(map_data, *guard_pos, *guard_dir, obstruction);
+      if obstructed {
+        count += 1;
+      }
+    }
+    count.into()
   }
-}
\ No newline at end of file
+}
+
+get_input::example_tests!(Day06);
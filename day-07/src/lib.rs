@@ -1,14 +1,19 @@
 use std::collections::HashSet;
 
-fn parse_input(input: &str) -> Vec<(i64, Vec<i64>)> {
+use get_input::{Answer, ParseError, Solution};
+
+fn parse_input(input: &str) -> Result<Vec<(i64, Vec<i64>)>, ParseError> {
   let mut parsed_input = Vec::new();
-  for line in input.lines() {
-    let parts: Vec<&str> = line.split(':').collect();
-    let target: i64 = parts[0].trim().parse().unwrap();
-    let nums: Vec<i64> = parts[1].trim().split_whitespace().map(|x| x.parse().unwrap()).collect();
+  for (index, line) in input.lines().enumerate() {
+    let at_line = |err: ParseError| err.at_line(index + 1);
+    let (target, nums) = line.split_once(':')
+      .ok_or_else(|| at_line(ParseError::new("expected `target: numbers`")))?;
+    let target: i64 = target.trim().parse().map_err(|err| at_line(ParseError::from(err)))?;
+    let nums: Vec<i64> = nums.split_whitespace().map(|x| x.parse()).collect::<Result<_, _>>()
+      .map_err(|err| at_line(ParseError::from(err)))?;
     parsed_input.push((target, nums));
   }
-  parsed_input 
+  Ok(parsed_input)
 }
 
 fn is_fixable_equation(target: i64, parts: &[i64], allow_concatenation: bool) -> i64 {
@@ -58,64 +63,40 @@ fn is_fixable_equation(target: i64, parts: &[i64], allow_concatenation: bool) ->
     }
     // if our potential targets contain 0, we have figured out a way to get to the target
     if potential_targets.contains(&0) {
-      return target
+      target
     } else {
-      return 0
+      0
     }
 }
 
 
-pub fn part1(input: &str) -> String {
-  let start = std::time::Instant::now();
-  let parsed_input = parse_input(input);
+pub struct Day07;
 
-  let mut count = 0;
-  for (target, parts) in parsed_input {
-    let result = is_fixable_equation(target, &parts, false);
-    count += result;
-  }
-  let elapsed = start.elapsed();
-  println!("Part 1 Time: {:?}", elapsed);
-  count.to_string()
-}
+impl Solution for Day07 {
+  // Each equation's test value and its numbers
+  type Parsed = Vec<(i64, Vec<i64>)>;
 
-pub fn part2(input: &str) -> String {
-  let start = std::time::Instant::now();
-  let parsed_input = parse_input(input);
-  
-  let mut count = 0;
-  for (target, parts) in parsed_input {
-    let result = is_fixable_equation(target, &parts, true);
-    count += result;
+  fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+    parse_input(input)
   }
-  let elapsed = start.elapsed();
-  println!("Part 2 Time: {:?}", elapsed);
-  count.to_string()
-}
 
-#[cfg(test)]
-mod tests {
-  use super::*;
-
-  const INPUT: &str = "190: 10 19
-3267: 81 40 27
-83: 17 5
-156: 15 6
-7290: 6 8 6 15
-161011: 16 10 13
-192: 17 8 14
-21037: 9 7 18 13
-292: 11 6 16 20";
-
-  #[test]
-  fn part1_works() {
-      let result = part1(INPUT);
-      assert_eq!(result, "3749");
+  fn part1(equations: &Self::Parsed) -> Answer {
+    let mut count = 0;
+    for (target, parts) in equations {
+      let result = is_fixable_equation(*target, parts, false);
+      count += result;
+    }
+    count.into()
   }
 
-  #[test]
-  fn part2_works() {
-      let result = part2(INPUT);
-      assert_eq!(result, "11387");
+  fn part2(equations: &Self::Parsed) -> Answer {
+    let mut count = 0;
+    for (target, parts) in equations {
+      let result = is_fixable_equation(*target, parts, true);
+      count += result;
+    }
+    count.into()
   }
-}
\ No newline at end of file
+}
+
+get_input::example_tests!(Day07);
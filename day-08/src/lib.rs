@@ -2,6 +2,8 @@ use std::collections::{HashMap, HashSet};
 // HashMap is used to group antennas by their frequency
 // HashSet is used to store unique antinode positions
 
+use get_input::{Answer, ParseError, PuzzleInput, Shape, Solution};
+
 // Define a struct to represent a position on the map with x and y coordinates
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct Position {
@@ -59,13 +61,22 @@ impl Line {
     }
 }
 
-pub fn part1(input: &str) -> String {
+/// The size of the map and the antennas on it, grouped by frequency.
+pub struct Map {
+    width: isize,
+    height: isize,
+    antennas: HashMap<char, Vec<Position>>,
+}
+
+fn parse_map(input: &str) -> Result<Map, ParseError> {
+    // Antinodes are bounded by the first row's width, so every row must match it
+    let input = PuzzleInput::new(input).expect(Shape::Rectangular)?;
     // Parse the input into a vector of string slices, each representing a row of the map
     let map: Vec<&str> = input.lines().collect();
 
     // Determine the height and width of the map
     let height = map.len() as isize;
-    let width = map[0].len() as isize;
+    let width = map[0].chars().count() as isize;
 
     // Create a HashMap to group antennas by their frequency
     let mut antennas: HashMap<char, Vec<Position>> = HashMap::new();
@@ -74,17 +85,41 @@ pub fn part1(input: &str) -> String {
     for (y, row) in map.iter().enumerate() {
         for (x, cell) in row.chars().enumerate() {
             if cell != '.' { // '.' represents an empty space; other characters are antennas
-                antennas.entry(cell).or_insert_with(Vec::new)
+                antennas.entry(cell).or_default()
                     .push(Position { x: x as isize, y: y as isize });
             }
         }
     }
+    Ok(Map { width, height, antennas })
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Parsed = Map;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_map(input)
+    }
+
+    fn part1(map: &Self::Parsed) -> Answer {
+        part1(map)
+    }
+
+    fn part2(map: &Self::Parsed) -> Answer {
+        part2(map)
+    }
+}
+
+fn part1(map: &Map) -> Answer {
+    let Map { width, height, antennas } = map;
+    let (width, height) = (*width, *height);
 
     // Create a HashSet to store unique antinode positions
     let mut antinodes: HashSet<Position> = HashSet::new();
 
     // Iterate over each frequency and its corresponding list of antennas
-    for (_freq, antenna_list) in &antennas {
+    for antenna_list in antennas.values() {
         let n = antenna_list.len();
         if n < 2 {
             continue; // Skip frequencies with fewer than two antennas, as they cannot form antinodes
@@ -120,31 +155,17 @@ pub fn part1(input: &str) -> String {
             }
         }
     }
-    antinodes.len().to_string()
+    antinodes.len().into()
 }
 
-pub fn part2(input: &str) -> String {
-    // Parse the input into a vector of string slices, each representing a row of the map
-    let map: Vec<&str> = input.lines().collect();
-
-    // Create a HashMap to group antennas by their frequency
-    let mut antennas: HashMap<char, Vec<Position>> = HashMap::new();
-
-    // Iterate over each row and column to identify antennas and their positions
-    for (y, row) in map.iter().enumerate() {
-        for (x, cell) in row.chars().enumerate() {
-            if cell != '.' { // '.' represents an empty space; other characters are antennas
-                antennas.entry(cell).or_insert_with(Vec::new)
-                    .push(Position { x: x as isize, y: y as isize });
-            }
-        }
-    }
+fn part2(map: &Map) -> Answer {
+    let Map { width, height, antennas } = map;
 
     // Create a HashSet to store unique antinode positions
     let mut antinodes: HashSet<Position> = HashSet::new();
 
     // Iterate over each frequency and its corresponding list of antennas
-    for (_freq, antenna_list) in &antennas {
+    for antenna_list in antennas.values() {
         let n = antenna_list.len();
         if n < 2 {
             continue; // Skip frequencies with fewer than two antennas, as they cannot form lines
@@ -165,9 +186,9 @@ pub fn part2(input: &str) -> String {
         // Iterate over each unique line to identify all positions lying on that line
         for line in lines {
             // Iterate through each row and column of the map
-            for (x, row) in map.iter().enumerate() {
-                for (y, _cell) in row.chars().enumerate() {
-                    let p = Position { x: x as isize, y: y as isize }; // Current position
+            for y in 0..*height {
+                for x in 0..*width {
+                    let p = Position { x, y }; // Current position
 
                     // Check if the current position lies exactly on the line using the line equation
                     if line.a * p.x + line.b * p.y + line.c == 0 {
@@ -177,36 +198,8 @@ pub fn part2(input: &str) -> String {
             }
         }
     }
-    antinodes.len().to_string()
+    antinodes.len().into()
 }
 
 
-#[cfg(test)]
-mod tests {
-  use super::*;
-
-  const INPUT: &str = "............
-........0...
-.....0......
-.......0....
-....0.......
-......A.....
-............
-............
-........A...
-.........A..
-............
-............";
-
-  #[test]
-  fn part1_works() {
-      let result = part1(INPUT);
-      assert_eq!(result, "14");
-  }
-
-  #[test]
-  fn part2_works() {
-      let result = part2(INPUT);
-      assert_eq!(result, "70");
-  }
-}
\ No newline at end of file
+get_input::example_tests!(Day08);
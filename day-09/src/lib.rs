@@ -1,3 +1,5 @@
+use get_input::{Answer, ParseError, PuzzleInput, Shape, Solution};
+
 #[derive(Debug)]
 // Define an enum `Segment` to represent either a file segment or a free space segment
 enum Segment {
@@ -20,7 +22,7 @@ struct FreeSpan {
     length: usize, 
 }
 
-fn collect_files_and_free_spans(expanded: &Vec<Option<usize>>) -> (Vec<FileInfo>, Vec<FreeSpan>) {
+fn collect_files_and_free_spans(expanded: &[Option<usize>]) -> (Vec<FileInfo>, Vec<FreeSpan>) {
     let mut files = Vec::new();        
     let mut free_spans = Vec::new();   
     let mut i = 0;                      
@@ -55,18 +57,18 @@ fn collect_files_and_free_spans(expanded: &Vec<Option<usize>>) -> (Vec<FileInfo>
     (files, free_spans) 
 }
 
-fn parse_input_in_segments(input: &str) -> Vec<Segment> {
+fn parse_input_in_segments(input: &str) -> Result<Vec<Segment>, ParseError> {
     let mut segments = Vec::new(); 
-    let mut chars = input.chars(); 
+    // The disk map is one line of digits; anything else is a corrupted input
+    let input = PuzzleInput::new(input).expect(Shape::SingleLine)?;
+    let chars = input.chars(); 
     let mut file_id = 0; 
     let mut is_file = true; 
 
 
-    while let Some(c) = chars.next() {
-        if !c.is_digit(10) {
-            continue; 
-        }
-        let length = c.to_digit(10).unwrap() as usize; 
+    for c in chars {
+        let length = c.to_digit(10)
+            .ok_or_else(|| ParseError::new(format!("expected a digit, found {:?}", c)))? as usize; 
         if is_file {
             
             segments.push(Segment::File(file_id, length)); 
@@ -78,7 +80,7 @@ fn parse_input_in_segments(input: &str) -> Vec<Segment> {
         is_file = !is_file; 
     }
 
-    segments
+    Ok(segments)
 }
 
 
@@ -101,7 +103,7 @@ fn expand_segments(segments: &Vec<Segment>) -> Vec<Option<usize>> {
     expanded 
 }
 
-fn calculate_checksum(expanded: &Vec<Option<usize>>) -> usize {
+fn calculate_checksum(expanded: &[Option<usize>]) -> usize {
     let total = expanded.iter().enumerate().map(|(i, id)| match id {
         Some(id) => i * id, // Multiply the index by the file ID if occupied
         None => 0,          // Add zero if the block is free
@@ -109,7 +111,7 @@ fn calculate_checksum(expanded: &Vec<Option<usize>>) -> usize {
     total.sum() 
 }
 
-fn move_files(expanded: &mut Vec<Option<usize>>) {
+fn move_files(expanded: &mut [Option<usize>]) {
     let mut start_index = 0; // Initialize the start pointer at the beginning
     let mut end_index = expanded.len() - 1; // Initialize the end pointer at the end
 
@@ -129,11 +131,11 @@ fn move_files(expanded: &mut Vec<Option<usize>>) {
 /// This function collects file and free span information, sorts the files,
 /// and moves them to the leftmost suitable free spans.
 /// It also updates the free spans accordingly.
-fn move_files_part2(expanded: &mut Vec<Option<usize>>) {
+fn move_files_part2(expanded: &mut [Option<usize>]) {
     let (mut files, mut free_spans) = collect_files_and_free_spans(expanded); 
     
     // Sort files in descending order of `file_id` to prioritize moving larger or newer files first
-    files.sort_unstable_by(|a, b| b.file_id.cmp(&a.file_id));
+    files.sort_unstable_by_key(|file| std::cmp::Reverse(file.file_id));
 
     for file in &files {
         // Find the position of the first free span that can accommodate the file's size
@@ -165,44 +167,31 @@ fn move_files_part2(expanded: &mut Vec<Option<usize>>) {
     }
 }
 
-pub fn part1(input: &str) -> String {
-    let elasp = std::time::Instant::now();
-    let segments = parse_input_in_segments(input); // Parse the input into segments
-    let mut expanded = expand_segments(&segments); // Expand the segments into blocks
-    move_files(&mut expanded);
-    let checksum = calculate_checksum(&expanded);
-    let elasp = elasp.elapsed();
-    println!("Time: {:?}", elasp);
-    checksum.to_string()
-}
+pub struct Day09;
 
-pub fn part2(input: &str) -> String {
-    let elasp = std::time::Instant::now();
-    let segments = parse_input_in_segments(input); // Parse the input into segments
-    let mut expanded = expand_segments(&segments); // Expand the segments into blocks
-    move_files_part2(&mut expanded);
-    let checksum = calculate_checksum(&expanded);
-
-    let elasp = elasp.elapsed();
-    println!("Time: {:?}", elasp);
-    checksum.to_string()
-}
+impl Solution for Day09 {
+    // The disk's blocks: the ID of the file in each, or None if it is free
+    type Parsed = Vec<Option<usize>>;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const INPUT: &str = "2333133121414131402"; 
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let segments = parse_input_in_segments(input)?; // Parse the input into segments
+        Ok(expand_segments(&segments)) // Expand the segments into blocks
+    }
 
-    #[test]
-    fn part1_works() {
-        let result = part1(INPUT);
-        assert_eq!(result, "1928");
+    fn part1(expanded: &Self::Parsed) -> Answer {
+        let mut expanded = expanded.clone();
+        move_files(&mut expanded);
+        let checksum = calculate_checksum(&expanded);
+        checksum.into()
     }
 
-    #[test]
-    fn part2_works() {
-        let result = part2(INPUT);
-        assert_eq!(result, "2858");
+    fn part2(expanded: &Self::Parsed) -> Answer {
+        let mut expanded = expanded.clone();
+        move_files_part2(&mut expanded);
+        let checksum = calculate_checksum(&expanded);
+
+        checksum.into()
     }
 }
+
+get_input::example_tests!(Day09);
@@ -0,0 +1,119 @@
+use std::fmt;
+
+/// A puzzle answer: almost always an integer, occasionally a code or a word.
+///
+/// Integers compare by value whatever their variant, so `Answer::Signed(1)` equals
+/// `Answer::Unsigned(1)`. Expected answers read from a file or a page go
+/// through `Answer::parse`, which keeps surrounding whitespace from mattering.
+#[derive(Debug, Clone, Eq)]
+pub enum Answer {
+    Signed(i64),
+    Unsigned(u64),
+    Text(String),
+}
+
+impl Answer {
+    /// Reads an answer written out as text: a canonical decimal integer (no sign on
+    /// positives, no leading zeros) becomes a number, anything else stays text.
+    pub fn parse(text: &str) -> Answer {
+        let text = text.trim();
+        let canonical = |number: String| number == text;
+        if let Ok(value) = text.parse::<u64>() {
+            if canonical(value.to_string()) {
+                return Answer::Unsigned(value);
+            }
+        }
+        if let Ok(value) = text.parse::<i64>() {
+            if canonical(value.to_string()) {
+                return Answer::Signed(value);
+            }
+        }
+        Answer::Text(text.to_string())
+    }
+
+    /// The value of an integer answer, wide enough to hold either variant.
+    pub fn as_integer(&self) -> Option<i128> {
+        match *self {
+            Answer::Signed(value) => Some(value.into()),
+            Answer::Unsigned(value) => Some(value.into()),
+            Answer::Text(_) => None,
+        }
+    }
+
+    /// Whether this is the answer `expected` writes out, e.g. an `examples/` fixture.
+    pub fn matches(&self, expected: &str) -> bool {
+        *self == Answer::parse(expected)
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Answer::Text(a), Answer::Text(b)) => a == b,
+            _ => match (self.as_integer(), other.as_integer()) {
+                (Some(a), Some(b)) => a == b,
+                _ => false,
+            },
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Signed(value) => write!(f, "{}", value),
+            Answer::Unsigned(value) => write!(f, "{}", value),
+            Answer::Text(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+macro_rules! from_integer {
+    ($variant:ident as $wide:ty: $($int:ty),*) => {
+        $(impl From<$int> for Answer {
+            fn from(value: $int) -> Self {
+                Answer::$variant(value as $wide)
+            }
+        })*
+    };
+}
+
+from_integer!(Signed as i64: i8, i16, i32, i64, isize);
+from_integer!(Unsigned as u64: u8, u16, u32, u64, usize);
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::Text(text.trim().to_string())
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::from(text.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integers_compare_by_value() {
+        assert_eq!(Answer::from(3749), Answer::from(3749usize));
+        assert_eq!(Answer::from(-2i64), Answer::Signed(-2));
+        assert_ne!(Answer::from(-1), Answer::from(u64::MAX));
+        assert_ne!(Answer::from(42), Answer::from("42"));
+        assert_eq!(Answer::from(7u8).to_string(), "7");
+    }
+
+    #[test]
+    fn parse_reads_canonical_integers_and_keeps_the_rest_as_text() {
+        assert_eq!(Answer::parse("3749\n"), Answer::Unsigned(3749));
+        assert_eq!(Answer::parse(" -12 "), Answer::Signed(-12));
+        assert_eq!(Answer::parse("01"), Answer::Text("01".to_string()));
+        assert_eq!(Answer::parse("+5"), Answer::Text("+5".to_string()));
+        assert_eq!(Answer::parse("LGHZKRCT").to_string(), "LGHZKRCT");
+        assert!(Answer::from(11usize).matches("11\n"));
+        assert!(!Answer::from(11usize).matches("12"));
+    }
+}
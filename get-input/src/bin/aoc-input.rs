@@ -0,0 +1,324 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use get_input::{
+    init_logging, write_examples, Cache, FetchError, LevelFilter, Profiles, PuzzleId,
+    SessionStatus, DEFAULT_YEAR, KEY_FILE_ENV_VAR, PASSPHRASE_ENV_VAR,
+};
+
+const USAGE: &str = "Usage: aoc-input [-q | -v | -vv] <command> [options]
+
+  -q, -v, -vv                Log only warnings, or also debug or trace output
+                             (AOC_LOG=<level> overrides)
+
+Commands:
+  fetch --day N [--year Y] [--profile NAME | --all-profiles]
+                             Print a puzzle input, or fetch it for every profile in
+                             ~/.config/aoc/profiles and report where each was cached
+  profiles                   List the profiles that have a session file
+  prefetch [--year Y]        Download every unlocked day's input into the cache, for
+                             later use with AOC_OFFLINE=1
+  verify-cache [--dry-run]   Move cached inputs that are really error pages into quarantine
+  describe --day N [--year Y] [--out FILE]
+                             Print a puzzle description as Markdown, or write it to FILE;
+                             it is kept in day-NN/puzzle.md when day-NN exists
+  examples --day N [--year Y] [--dir DIR]
+                             Write the puzzle's examples as test fixtures under DIR/examples
+                             (default day-NN), keeping files that already exist
+  leaderboard --id ID [--year Y] [--day N]
+                             Show a private leaderboard, or one day's completion times
+  stars [--year Y]           Show the stars earned on each unlocked day
+  session                    Check that the session token is still valid
+  encrypt-cache [--legacy-dir DIR] [--delete-legacy]
+                             Encrypt plaintext cached inputs, every profile's included,
+                             with the key from AOC_CACHE_PASSPHRASE or AOC_CACHE_KEY_FILE,
+                             first importing input_day_N.txt files found in DIR or
+                             DIR/day-NN (and DIR/day-NN/input.txt); --delete-legacy
+                             then deletes every such file that matches the cached input";
+
+fn main() -> ExitCode {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let verbosity = match args.first().map(String::as_str) {
+        Some("-q") => Some(LevelFilter::Warn),
+        Some("-v") => Some(LevelFilter::Debug),
+        Some("-vv") => Some(LevelFilter::Trace),
+        _ => None,
+    };
+    if verbosity.is_some() {
+        args.remove(0);
+    }
+    init_logging(verbosity.unwrap_or(LevelFilter::Info));
+
+    let (command, options) = match args.split_first() {
+        Some((command, options)) => (command.as_str(), options),
+        None => {
+            println!("{}", USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let result = match command {
+        "fetch" => fetch(options),
+        "profiles" => profiles(),
+        "prefetch" => prefetch(options),
+        "verify-cache" => verify_cache(options),
+        "describe" => describe(options),
+        "examples" => examples(options),
+        "leaderboard" => leaderboard(options),
+        "stars" => stars(options),
+        "session" => session(),
+        "encrypt-cache" => encrypt_cache(options),
+        _ => {
+            println!("{}", USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("aoc-input {}: {}", command, err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn fetch(options: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    let id = puzzle_id(options)?;
+    if options.iter().any(|option| option == "--all-profiles") {
+        let results = get_input::fetch_input_for_all_profiles(id)?;
+        if results.is_empty() {
+            return Err(
+                "no profiles: write session tokens to ~/.config/aoc/profiles/<name>".into(),
+            );
+        }
+        let mut failed = 0;
+        for (profile, input) in &results {
+            match input {
+                Ok(input) => {
+                    let cache = Cache::from_env().with_profile(Some(profile.clone()));
+                    println!(
+                        "{}: {} lines, cached in {}",
+                        profile,
+                        input.lines().count(),
+                        cache.puzzle_dir(id).display()
+                    );
+                }
+                Err(err) => {
+                    println!("{}: {}", profile, err);
+                    failed += 1;
+                }
+            }
+        }
+        if failed > 0 {
+            return Err(format!("{} of {} profile(s) failed", failed, results.len()).into());
+        }
+        return Ok(());
+    }
+
+    let input = match option_value(options, "--profile") {
+        Some(profile) => get_input::fetch_input_for_profile(id, profile)?,
+        None => get_input::fetch_input_for(id)?,
+    };
+    println!("{}", input);
+    Ok(())
+}
+
+fn profiles() -> Result<(), Box<dyn std::error::Error>> {
+    let profiles = Profiles::from_env().ok_or("no config directory: set HOME")?;
+    let names = profiles.list()?;
+    for profile in &names {
+        println!("{}: {}", profile, profiles.session_path(profile).display());
+    }
+    println!("{} profile(s) in {}", names.len(), profiles.dir().display());
+    Ok(())
+}
+
+fn prefetch(options: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    let year = year(options)?;
+    let fetched = get_input::prefetch_year(year)?;
+    for id in &fetched {
+        println!("{}: downloaded", id);
+    }
+    println!(
+        "Downloaded {} input(s) for {} into {}",
+        fetched.len(),
+        year,
+        Cache::from_env().root().display()
+    );
+    Ok(())
+}
+
+fn verify_cache(options: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    let dry_run = options.iter().any(|option| option == "--dry-run");
+    let cache = Cache::from_env();
+
+    let quarantined = cache.verify(dry_run)?;
+    for bad in &quarantined {
+        if dry_run {
+            println!("{}: {} ({})", bad.id, bad.from.display(), bad.reason);
+        } else {
+            println!("{}: moved to {} ({})", bad.id, bad.to.display(), bad.reason);
+        }
+    }
+    println!(
+        "Checked {}: {} bad input(s)",
+        cache.root().display(),
+        quarantined.len()
+    );
+    Ok(())
+}
+
+fn describe(options: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    let id = puzzle_id(options)?;
+    let markdown = get_input::fetch_description(id)?;
+    match option_value(options, "--out") {
+        Some(path) => {
+            fs::write(path, markdown)?;
+            println!("Wrote {} to {}", id, path);
+        }
+        None => print!("{}", markdown),
+    }
+    Ok(())
+}
+
+fn examples(options: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    let id = puzzle_id(options)?;
+    let dir = match option_value(options, "--dir") {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(format!("day-{:02}", id.day())),
+    };
+
+    let examples = get_input::fetch_examples(id)?;
+    if examples.is_empty() {
+        return Err(format!("no examples found for {}", id).into());
+    }
+    for path in write_examples(&dir, &examples)? {
+        println!("Wrote {}", path.display());
+    }
+    Ok(())
+}
+
+fn leaderboard(options: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    let id = option_value(options, "--id").ok_or("--id is required")?;
+    let id = id
+        .parse()
+        .map_err(|_| format!("invalid leaderboard id {:?}", id))?;
+    let year = year(options)?;
+
+    let leaderboard = get_input::fetch_leaderboard(year, id)?;
+    match option_value(options, "--day") {
+        Some(day) => {
+            let day = PuzzleId::parse_day(year, day)?.day();
+            print!("{}", leaderboard.render_day(day));
+        }
+        None => print!("{}", leaderboard.render_overview()),
+    }
+    Ok(())
+}
+
+fn stars(options: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    let calendar = get_input::fetch_calendar(year(options)?)?;
+    for (day, &stars) in &calendar.stars {
+        println!("Day {:>2}: {}", day, "*".repeat(usize::from(stars)));
+    }
+    println!("{} stars in {}", calendar.total(), calendar.year);
+    Ok(())
+}
+
+fn session() -> Result<(), Box<dyn std::error::Error>> {
+    match get_input::validate_session()? {
+        SessionStatus::Valid { user, source } => {
+            println!("Logged in as {} (token from {})", user, source);
+            Ok(())
+        }
+        SessionStatus::Expired { source } => Err(FetchError::ExpiredSession { source }.into()),
+    }
+}
+
+fn encrypt_cache(options: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    let cache = Cache::from_env();
+    if !cache.is_encrypted() {
+        return Err(format!("set {} or {} first", PASSPHRASE_ENV_VAR, KEY_FILE_ENV_VAR).into());
+    }
+    let delete_legacy = options.iter().any(|option| option == "--delete-legacy");
+
+    let mut imported = 0;
+    if let Some(dir) = option_value(options, "--legacy-dir") {
+        let dir = Path::new(dir);
+        for day in 1..=25 {
+            let id = PuzzleId::new(DEFAULT_YEAR, day)?;
+            let migrated = cache.migrate_legacy(id, dir)?;
+            let cached = cache.read_input(id)?;
+            for legacy_file in get_input::legacy_inputs(id, dir) {
+                let was_imported = migrated.as_ref() == Some(&legacy_file);
+                if was_imported {
+                    println!("{}: imported {}", id, legacy_file.display());
+                    imported += 1;
+                }
+                if !delete_legacy {
+                    if !was_imported {
+                        println!("{}: skipped {}", id, legacy_file.display());
+                    }
+                    continue;
+                }
+
+                // Only copies the cache can give back are deleted
+                let contents = fs::read_to_string(&legacy_file)?;
+                if cached.as_deref() == Some(contents.as_str()) {
+                    fs::remove_file(&legacy_file)?;
+                    println!("{}: deleted {}", id, legacy_file.display());
+                } else {
+                    println!(
+                        "{}: kept {}, it differs from the cached input",
+                        id,
+                        legacy_file.display()
+                    );
+                }
+            }
+        }
+    }
+
+    // Profiles keep their inputs in namespaces of their own under the root
+    let mut encrypted = 0;
+    for namespace in cache.namespaces()? {
+        for id in namespace.encrypt_plaintext()? {
+            println!(
+                "{}: encrypted {}",
+                id,
+                namespace.encrypted_input_path(id).display()
+            );
+            encrypted += 1;
+        }
+    }
+    println!(
+        "Encrypted {} input(s) in {}",
+        imported + encrypted,
+        cache.root().display()
+    );
+    Ok(())
+}
+
+fn puzzle_id(options: &[String]) -> Result<PuzzleId, Box<dyn std::error::Error>> {
+    let day = option_value(options, "--day").ok_or("--day is required")?;
+    Ok(PuzzleId::parse_day(year(options)?, day)?)
+}
+
+fn year(options: &[String]) -> Result<u16, Box<dyn std::error::Error>> {
+    match option_value(options, "--year") {
+        Some(year) => Ok(year
+            .parse()
+            .map_err(|_| format!("invalid year {:?}", year))?),
+        None => Ok(DEFAULT_YEAR),
+    }
+}
+
+fn option_value<'a>(options: &'a [String], name: &str) -> Option<&'a str> {
+    options
+        .iter()
+        .position(|option| option == name)
+        .and_then(|index| options.get(index + 1))
+        .map(String::as_str)
+}
@@ -0,0 +1,663 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+use std::time::SystemTime;
+
+use log::warn;
+
+use crate::crypto::{CacheKey, KeySource, KEY_FILE_ENV_VAR, PASSPHRASE_ENV_VAR};
+use crate::profile::Profile;
+use crate::puzzle::{PuzzleId, DEFAULT_YEAR};
+use crate::validate::{validate_input, InvalidInput};
+
+/// Environment variable that overrides the cache root.
+pub const CACHE_DIR_ENV_VAR: &str = "AOC_CACHE_DIR";
+
+/// A cached input that failed validation and was moved out of the cache.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Quarantined {
+    pub id: PuzzleId,
+    pub from: PathBuf,
+    pub to: PathBuf,
+    pub reason: InvalidInput,
+}
+
+/// On-disk store of puzzle inputs, laid out as `<root>/<year>/<day>/input.txt`.
+/// With encryption configured, inputs are stored as `input.enc` instead. A profile's
+/// puzzles live under `<root>/profiles/<name>`; the request throttle and encryption
+/// salt stay shared by all profiles.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cache {
+    root: PathBuf,
+    profile: Option<Profile>,
+    encryption: Option<Encryption>,
+}
+
+// The key is derived on first use, since that is deliberately slow
+#[derive(Debug, Clone)]
+struct Encryption {
+    source: KeySource,
+    key: Arc<OnceLock<CacheKey>>,
+}
+
+impl PartialEq for Encryption {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+    }
+}
+
+impl Eq for Encryption {}
+
+impl Cache {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Cache {
+            root: root.into(),
+            profile: None,
+            encryption: None,
+        }
+    }
+
+    /// Uses `AOC_CACHE_DIR`, then `$XDG_CACHE_HOME/aoc`, then `~/.cache/aoc`, encrypting
+    /// inputs when `AOC_CACHE_KEY_FILE` or `AOC_CACHE_PASSPHRASE` is set.
+    pub fn from_env() -> Self {
+        let root = match env::var_os(CACHE_DIR_ENV_VAR) {
+            Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+            _ => default_root(),
+        };
+        Cache::new(root).with_encryption(KeySource::from_env())
+    }
+
+    /// Encrypts inputs written from now on with a key derived from `source`;
+    /// `None` stores them in plaintext.
+    pub fn with_encryption(mut self, source: Option<KeySource>) -> Self {
+        self.encryption = source.map(|source| Encryption {
+            source,
+            key: Arc::new(OnceLock::new()),
+        });
+        self
+    }
+
+    pub fn is_encrypted(&self) -> bool {
+        self.encryption.is_some()
+    }
+
+    /// Switches to a profile's namespace; `None` is the default account's.
+    pub fn with_profile(mut self, profile: Option<Profile>) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    pub fn profile(&self) -> Option<&Profile> {
+        self.profile.as_ref()
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// This cache followed by one for every profile with puzzles under the root, so a
+    /// whole-cache operation such as encryption does not miss a profile's inputs.
+    pub fn namespaces(&self) -> io::Result<Vec<Cache>> {
+        let mut profiles: Vec<Profile> = read_dir_or_empty(&self.root.join("profiles"))?
+            .into_iter()
+            .filter(|entry| entry.path().is_dir())
+            .filter_map(|entry| Profile::new(entry.file_name().to_str()?).ok())
+            .filter(|profile| self.profile.as_ref() != Some(profile))
+            .collect();
+        profiles.sort();
+
+        let mut namespaces = vec![self.clone()];
+        namespaces.extend(
+            profiles
+                .into_iter()
+                .map(|profile| self.clone().with_profile(Some(profile))),
+        );
+        Ok(namespaces)
+    }
+
+    /// Where this cache's puzzles are stored: the root, or the profile's directory in it.
+    pub fn namespace(&self) -> PathBuf {
+        match &self.profile {
+            Some(profile) => self.root.join("profiles").join(profile.name()),
+            None => self.root.clone(),
+        }
+    }
+
+    pub fn puzzle_dir(&self, id: PuzzleId) -> PathBuf {
+        self.namespace()
+            .join(id.year().to_string())
+            .join(format!("{:02}", id.day()))
+    }
+
+    pub fn input_path(&self, id: PuzzleId) -> PathBuf {
+        self.puzzle_dir(id).join("input.txt")
+    }
+
+    pub fn encrypted_input_path(&self, id: PuzzleId) -> PathBuf {
+        self.puzzle_dir(id).join("input.enc")
+    }
+
+    /// The random salt the encryption key is derived with. Without it encrypted
+    /// inputs cannot be read and have to be downloaded again.
+    pub fn salt_path(&self) -> PathBuf {
+        self.root.join("encryption.salt")
+    }
+
+    pub fn has_input(&self, id: PuzzleId) -> bool {
+        self.encrypted_input_path(id).is_file() || self.input_path(id).is_file()
+    }
+
+    /// Where the answers submitted for a puzzle are recorded.
+    pub fn ledger_path(&self, id: PuzzleId) -> PathBuf {
+        self.puzzle_dir(id).join("answers.tsv")
+    }
+
+    /// The Markdown puzzle description, cached next to the input when the day has
+    /// no `day-NN` directory to keep it in.
+    pub fn description_path(&self, id: PuzzleId) -> PathBuf {
+        self.puzzle_dir(id).join("puzzle.md")
+    }
+
+    /// Returns the cached input, or `None` if it has not been downloaded yet.
+    /// Encrypted inputs are decrypted, which needs the key to be configured.
+    pub fn read_input(&self, id: PuzzleId) -> io::Result<Option<String>> {
+        let encrypted_path = self.encrypted_input_path(id);
+        let sealed = match fs::read(&encrypted_path) {
+            Ok(sealed) => sealed,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                return read_optional(&self.input_path(id));
+            }
+            Err(err) => return Err(err),
+        };
+
+        let key = self.key()?.ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                format!(
+                    "{} is encrypted; set {} or {}",
+                    encrypted_path.display(),
+                    PASSPHRASE_ENV_VAR,
+                    KEY_FILE_ENV_VAR
+                ),
+            )
+        })?;
+        let input = key.open(&id.to_string(), &sealed)?;
+        String::from_utf8(input)
+            .map(Some)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    /// Saves an input, encrypted if the cache has a key, and returns where it went.
+    pub fn write_input(&self, id: PuzzleId, input: &str) -> io::Result<PathBuf> {
+        let Some(key) = self.key()? else {
+            return write_file(self.input_path(id), input);
+        };
+
+        let path = self.encrypted_input_path(id);
+        fs::create_dir_all(self.puzzle_dir(id))?;
+        fs::write(&path, key.seal(&id.to_string(), input.as_bytes())?)?;
+        remove_if_exists(&self.input_path(id))?;
+        Ok(path)
+    }
+
+    /// Encrypts every plaintext input in this namespace, returning the puzzles that
+    /// were; see `namespaces` for the profiles' inputs. Fails when no key is configured.
+    pub fn encrypt_plaintext(&self) -> io::Result<Vec<PuzzleId>> {
+        if !self.is_encrypted() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "no encryption key: set {} or {}",
+                    PASSPHRASE_ENV_VAR, KEY_FILE_ENV_VAR
+                ),
+            ));
+        }
+
+        let mut encrypted = Vec::new();
+        for id in self.cached_ids()? {
+            if let Some(input) = read_optional(&self.input_path(id))? {
+                self.write_input(id, &input)?;
+                encrypted.push(id);
+            }
+        }
+        Ok(encrypted)
+    }
+
+    fn key(&self) -> io::Result<Option<&CacheKey>> {
+        let Some(encryption) = &self.encryption else {
+            return Ok(None);
+        };
+        if encryption.key.get().is_none() {
+            let key = CacheKey::derive(&encryption.source, &self.salt_path())?;
+            let _ = encryption.key.set(key);
+        }
+        Ok(encryption.key.get())
+    }
+
+    /// When the next request may be sent, shared by every process using this cache.
+    pub fn throttle_path(&self) -> PathBuf {
+        self.root.join("last-request")
+    }
+
+    /// A private leaderboard's JSON, shared by every day of the year.
+    pub fn leaderboard_path(&self, year: u16, leaderboard: u64) -> PathBuf {
+        self.namespace()
+            .join(year.to_string())
+            .join("leaderboard")
+            .join(format!("{}.json", leaderboard))
+    }
+
+    /// Returns the cached leaderboard JSON and when it was written.
+    pub fn read_leaderboard(
+        &self,
+        year: u16,
+        leaderboard: u64,
+    ) -> io::Result<Option<(String, SystemTime)>> {
+        let path = self.leaderboard_path(year, leaderboard);
+        match read_optional(&path)? {
+            Some(json) => Ok(Some((json, fs::metadata(&path)?.modified()?))),
+            None => Ok(None),
+        }
+    }
+
+    pub fn write_leaderboard(
+        &self,
+        year: u16,
+        leaderboard: u64,
+        json: &str,
+    ) -> io::Result<PathBuf> {
+        write_file(self.leaderboard_path(year, leaderboard), json)
+    }
+
+    /// Every puzzle with a cached input, in year and day order.
+    pub fn cached_ids(&self) -> io::Result<Vec<PuzzleId>> {
+        let mut ids = Vec::new();
+        for year_dir in read_dir_or_empty(&self.namespace())? {
+            let year = match year_dir.file_name().to_str().and_then(|s| s.parse().ok()) {
+                Some(year) => year,
+                None => continue,
+            };
+            for day_dir in read_dir_or_empty(&year_dir.path())? {
+                let day = day_dir.file_name().to_str().and_then(|s| s.parse().ok());
+                if let Some(id) = day.and_then(|day| PuzzleId::new(year, day).ok()) {
+                    if self.has_input(id) {
+                        ids.push(id);
+                    }
+                }
+            }
+        }
+        ids.sort();
+        Ok(ids)
+    }
+
+    /// Where bad inputs are moved, mirroring the cache layout.
+    pub fn quarantine_dir(&self) -> PathBuf {
+        self.namespace().join("quarantine")
+    }
+
+    /// Validates every cached input and moves the ones that are really error pages
+    /// into the quarantine directory, so the next fetch downloads them again.
+    /// With `dry_run` the bad files are reported but left in place.
+    pub fn verify(&self, dry_run: bool) -> io::Result<Vec<Quarantined>> {
+        let mut quarantined = Vec::new();
+        for id in self.cached_ids()? {
+            let Some(input) = self.read_input(id)? else {
+                continue;
+            };
+            if let Err(reason) = validate_input(&input) {
+                let from = match self.encrypted_input_path(id) {
+                    encrypted if encrypted.is_file() => encrypted,
+                    _ => self.input_path(id),
+                };
+                let to = self
+                    .quarantine_dir()
+                    .join(id.year().to_string())
+                    .join(format!("{:02}", id.day()))
+                    .join(from.file_name().unwrap());
+                if !dry_run {
+                    fs::create_dir_all(to.parent().unwrap())?;
+                    fs::rename(&from, &to)?;
+                }
+                quarantined.push(Quarantined {
+                    id,
+                    from,
+                    to,
+                    reason,
+                });
+            }
+        }
+        Ok(quarantined)
+    }
+
+    /// Copies a per-day file from before the shared cache existed (`input_day_N.txt`
+    /// in `dir` or `dir/day-NN`) into the cache. Returns the file that was migrated;
+    /// empty files and error pages are left where they are.
+    pub fn migrate_legacy(&self, id: PuzzleId, dir: &Path) -> io::Result<Option<PathBuf>> {
+        if self.has_input(id) {
+            return Ok(None);
+        }
+
+        let Some((legacy_file, input)) = read_legacy_input(id, dir)? else {
+            return Ok(None);
+        };
+        self.write_input(id, &input)?;
+        Ok(Some(legacy_file))
+    }
+}
+
+impl Default for Cache {
+    fn default() -> Self {
+        Cache::from_env()
+    }
+}
+
+fn default_root() -> PathBuf {
+    match env::var_os("XDG_CACHE_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir).join("aoc"),
+        _ => match env::var_os("HOME") {
+            Some(home) => PathBuf::from(home).join(".cache").join("aoc"),
+            None => env::temp_dir().join("aoc"),
+        },
+    }
+}
+
+pub(crate) fn write_file(path: PathBuf, contents: &str) -> io::Result<PathBuf> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, contents)?;
+    Ok(path)
+}
+
+pub(crate) fn remove_if_exists(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+        _ => Ok(()),
+    }
+}
+
+pub(crate) fn read_optional(path: &Path) -> io::Result<Option<String>> {
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(Some(contents)),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+fn read_dir_or_empty(dir: &Path) -> io::Result<Vec<fs::DirEntry>> {
+    match fs::read_dir(dir) {
+        Ok(entries) => entries.collect(),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(err) => Err(err),
+    }
+}
+
+/// Every per-day file `Cache::migrate_legacy` would consider for a puzzle, in the
+/// order it tries them, whether or not the cache already has the input.
+pub fn legacy_inputs(id: PuzzleId, dir: &Path) -> Vec<PathBuf> {
+    legacy_candidates(id, dir)
+        .into_iter()
+        .filter(|candidate| candidate.is_file())
+        .collect()
+}
+
+/// The first per-day file left for a puzzle that holds a valid input, and its
+/// contents. Files that do not pass `validate_input` are skipped with a warning.
+pub fn read_legacy_input(id: PuzzleId, dir: &Path) -> io::Result<Option<(PathBuf, String)>> {
+    for file in legacy_candidates(id, dir) {
+        let input = match fs::read_to_string(&file) {
+            Ok(input) => input,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+            Err(err) => return Err(err),
+        };
+        match validate_input(&input) {
+            Ok(()) => return Ok(Some((file, input))),
+            Err(reason) => warn!("Ignoring {}: {}", file.display(), reason),
+        }
+    }
+    Ok(None)
+}
+
+fn legacy_candidates(id: PuzzleId, dir: &Path) -> Vec<PathBuf> {
+    let day_dir = dir.join(format!("day-{:02}", id.day()));
+    let mut names = vec![id.cache_file_name()];
+    // Files without a year in the name were all written for the 2024 event
+    if id.year() == DEFAULT_YEAR {
+        names.push(format!("input_day_{}.txt", id.day()));
+    }
+
+    let mut candidates = Vec::new();
+    for base in [dir, day_dir.as_path()] {
+        for name in &names {
+            candidates.push(base.join(name));
+        }
+    }
+    // The starter template used to keep each day's input next to its code
+    if id.year() == DEFAULT_YEAR {
+        candidates.push(day_dir.join("input.txt"));
+    }
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layout_is_year_then_day() {
+        let cache = Cache::new("/tmp/aoc");
+        let id = PuzzleId::new(2024, 7).unwrap();
+        assert_eq!(cache.input_path(id), PathBuf::from("/tmp/aoc/2024/07/input.txt"));
+    }
+
+    #[test]
+    fn profiles_have_their_own_namespace() {
+        let dir = tempfile::tempdir().unwrap();
+        let id = PuzzleId::new(2024, 7).unwrap();
+        let default = Cache::new(dir.path());
+        let alice = default
+            .clone()
+            .with_profile(Some(Profile::new("alice").unwrap()));
+        assert_eq!(
+            alice.input_path(id),
+            dir.path().join("profiles/alice/2024/07/input.txt")
+        );
+        assert_eq!(alice.throttle_path(), default.throttle_path());
+
+        alice.write_input(id, "alice\n").unwrap();
+        assert_eq!(alice.cached_ids().unwrap(), [id]);
+        assert!(default.cached_ids().unwrap().is_empty());
+        assert_eq!(default.read_input(id).unwrap(), None);
+    }
+
+    #[test]
+    fn read_missing_input_is_none() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = Cache::new(dir.path());
+        let id = PuzzleId::new(2024, 1).unwrap();
+        assert_eq!(cache.read_input(id).unwrap(), None);
+
+        cache.write_input(id, "3   4\n").unwrap();
+        assert_eq!(cache.read_input(id).unwrap(), Some("3   4\n".to_string()));
+    }
+
+    fn encrypted(root: &Path) -> Cache {
+        Cache::new(root).with_encryption(Some(KeySource::Passphrase("hunter2".to_string())))
+    }
+
+    #[test]
+    fn encrypted_inputs_are_unreadable_without_the_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = encrypted(dir.path());
+        let id = PuzzleId::new(2024, 1).unwrap();
+
+        let path = cache.write_input(id, "3   4\n").unwrap();
+        assert_eq!(path, cache.encrypted_input_path(id));
+        assert!(!fs::read(&path).unwrap().starts_with(b"3   4"));
+        assert_eq!(cache.read_input(id).unwrap().as_deref(), Some("3   4\n"));
+        assert_eq!(cache.cached_ids().unwrap(), vec![id]);
+
+        let plain = Cache::new(dir.path());
+        let err = plain.read_input(id).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::PermissionDenied);
+    }
+
+    #[test]
+    fn plaintext_inputs_are_encrypted_in_place() {
+        let dir = tempfile::tempdir().unwrap();
+        let id = PuzzleId::new(2024, 2).unwrap();
+        Cache::new(dir.path()).write_input(id, "7 6 4\n").unwrap();
+
+        assert!(Cache::new(dir.path()).encrypt_plaintext().is_err());
+        let cache = encrypted(dir.path());
+        assert_eq!(cache.read_input(id).unwrap().as_deref(), Some("7 6 4\n"));
+        assert_eq!(cache.encrypt_plaintext().unwrap(), vec![id]);
+        assert!(!cache.input_path(id).exists());
+        assert_eq!(cache.read_input(id).unwrap().as_deref(), Some("7 6 4\n"));
+        assert!(cache.encrypt_plaintext().unwrap().is_empty());
+    }
+
+    #[test]
+    fn profile_inputs_are_encrypted_too() {
+        let dir = tempfile::tempdir().unwrap();
+        let id = PuzzleId::new(2024, 3).unwrap();
+        let alice = Profile::new("alice").unwrap();
+        Cache::new(dir.path())
+            .write_input(id, "mul(2,4)\n")
+            .unwrap();
+        Cache::new(dir.path())
+            .with_profile(Some(alice.clone()))
+            .write_input(id, "mul(3,5)\n")
+            .unwrap();
+        fs::create_dir_all(dir.path().join("profiles/not a profile")).unwrap();
+
+        let namespaces = encrypted(dir.path()).namespaces().unwrap();
+        let profiles: Vec<_> = namespaces.iter().map(Cache::profile).collect();
+        assert_eq!(profiles, [None, Some(&alice)]);
+
+        for cache in &namespaces {
+            assert_eq!(cache.encrypt_plaintext().unwrap(), vec![id]);
+            assert!(!cache.input_path(id).exists());
+        }
+        assert_eq!(
+            namespaces[1].read_input(id).unwrap().as_deref(),
+            Some("mul(3,5)\n")
+        );
+    }
+
+    #[test]
+    fn legacy_inputs_are_found_even_once_cached() {
+        let work = tempfile::tempdir().unwrap();
+        let root = tempfile::tempdir().unwrap();
+        let id = PuzzleId::new(2024, 7).unwrap();
+        assert!(legacy_inputs(id, work.path()).is_empty());
+
+        fs::create_dir(work.path().join("day-07")).unwrap();
+        let old = work.path().join("day-07").join("input_day_7.txt");
+        let path = work.path().join("day-07").join("input.txt");
+        fs::write(&old, "190: 10 19\n").unwrap();
+        fs::write(&path, "190: 10 19\n").unwrap();
+        let cache = Cache::new(root.path());
+        assert_eq!(
+            cache.migrate_legacy(id, work.path()).unwrap(),
+            Some(old.clone())
+        );
+        assert_eq!(cache.migrate_legacy(id, work.path()).unwrap(), None);
+        assert_eq!(legacy_inputs(id, work.path()), [old, path]);
+    }
+
+    #[test]
+    fn migrates_legacy_files_from_day_directories() {
+        let work = tempfile::tempdir().unwrap();
+        let root = tempfile::tempdir().unwrap();
+        fs::create_dir(work.path().join("day-09")).unwrap();
+        fs::write(work.path().join("day-09").join("input_day_9.txt"), "2333133121414131402").unwrap();
+
+        let cache = Cache::new(root.path());
+        let id = PuzzleId::new(2024, 9).unwrap();
+        let migrated = cache.migrate_legacy(id, work.path()).unwrap();
+        assert_eq!(migrated, Some(work.path().join("day-09").join("input_day_9.txt")));
+        assert_eq!(cache.read_input(id).unwrap().as_deref(), Some("2333133121414131402"));
+
+        // A second run leaves the cached copy alone
+        assert_eq!(cache.migrate_legacy(id, work.path()).unwrap(), None);
+    }
+
+    #[test]
+    fn lists_cached_puzzles_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = Cache::new(dir.path());
+        let late = PuzzleId::new(2024, 10).unwrap();
+        let early = PuzzleId::new(2023, 2).unwrap();
+        cache.write_input(late, "0123\n").unwrap();
+        cache.write_input(early, "abc\n").unwrap();
+        fs::create_dir_all(dir.path().join("quarantine").join("2024")).unwrap();
+
+        assert_eq!(cache.cached_ids().unwrap(), vec![early, late]);
+    }
+
+    #[test]
+    fn verify_quarantines_error_pages() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = Cache::new(dir.path());
+        let good = PuzzleId::new(2024, 1).unwrap();
+        let bad = PuzzleId::new(2024, 2).unwrap();
+        cache.write_input(good, "3   4\n").unwrap();
+        cache
+            .write_input(
+                bad,
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+            )
+            .unwrap();
+
+        let dry_run = cache.verify(true).unwrap();
+        assert_eq!(dry_run.len(), 1);
+        assert!(cache.input_path(bad).exists());
+
+        let quarantined = cache.verify(false).unwrap();
+        assert_eq!(quarantined.len(), 1);
+        assert_eq!(quarantined[0].id, bad);
+        assert_eq!(
+            quarantined[0].reason,
+            InvalidInput::ErrorNotice("Puzzle inputs differ by user")
+        );
+        assert!(quarantined[0].to.exists());
+        assert_eq!(cache.read_input(bad).unwrap(), None);
+        assert_eq!(cache.read_input(good).unwrap().as_deref(), Some("3   4\n"));
+        assert!(cache.verify(false).unwrap().is_empty());
+    }
+
+    #[test]
+    fn yearless_legacy_files_only_count_for_2024() {
+        let work = tempfile::tempdir().unwrap();
+        let root = tempfile::tempdir().unwrap();
+        fs::write(work.path().join("input_day_1.txt"), "2024 input").unwrap();
+
+        let cache = Cache::new(root.path());
+        let id = PuzzleId::new(2023, 1).unwrap();
+        assert_eq!(cache.migrate_legacy(id, work.path()).unwrap(), None);
+    }
+
+    #[test]
+    fn empty_legacy_files_are_not_migrated() {
+        let work = tempfile::tempdir().unwrap();
+        let root = tempfile::tempdir().unwrap();
+        let day_dir = work.path().join("day-07");
+        fs::create_dir(&day_dir).unwrap();
+        fs::write(day_dir.join("input.txt"), "").unwrap();
+
+        let cache = Cache::new(root.path());
+        let id = PuzzleId::new(2024, 7).unwrap();
+        assert_eq!(cache.migrate_legacy(id, work.path()).unwrap(), None);
+        assert!(!cache.has_input(id));
+
+        // A valid copy elsewhere is still picked up
+        fs::write(day_dir.join("input_day_7.txt"), "190: 10 19\n").unwrap();
+        assert_eq!(
+            cache.migrate_legacy(id, work.path()).unwrap(),
+            Some(day_dir.join("input_day_7.txt"))
+        );
+    }
+}
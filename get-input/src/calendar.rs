@@ -0,0 +1,96 @@
+use std::collections::BTreeMap;
+
+use crate::html;
+use crate::puzzle::Part;
+
+/// The stars earned on each unlocked day of a year, as shown on the year's calendar.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Calendar {
+    pub year: u16,
+    /// Stars (0 to 2) by day; days that have not unlocked yet are absent.
+    pub stars: BTreeMap<u8, u8>,
+}
+
+impl Calendar {
+    /// Reads the calendar from a year page such as `/2024`. Returns `None` when the
+    /// page has no calendar.
+    pub fn from_page(year: u16, page: &str) -> Option<Self> {
+        let nodes = html::parse(page);
+        let calendar = html::find_all(&nodes, &|e| e.name == "pre" && e.has_class("calendar"));
+        let calendar = calendar.first()?;
+
+        // Unlocked days are links classed `calendar-dayN`, plus `calendar-complete`
+        // for one star or `calendar-verycomplete` for two
+        let mut stars = BTreeMap::new();
+        for link in calendar.find_all(&|e| e.name == "a") {
+            let Some(day) = link.attr("class").and_then(|classes| {
+                classes
+                    .split_whitespace()
+                    .find_map(|class| class.strip_prefix("calendar-day")?.parse::<u8>().ok())
+            }) else {
+                continue;
+            };
+            let earned = if link.has_class("calendar-verycomplete") {
+                2
+            } else if link.has_class("calendar-complete") {
+                1
+            } else {
+                0
+            };
+            stars.insert(day, earned);
+        }
+        Some(Calendar { year, stars })
+    }
+
+    pub fn stars(&self, day: u8) -> u8 {
+        self.stars.get(&day).copied().unwrap_or(0)
+    }
+
+    pub fn is_solved(&self, day: u8, part: Part) -> bool {
+        self.stars(day) >= part.level()
+    }
+
+    /// How many parts of a day's description can be read: part two shows once part
+    /// one is solved.
+    pub fn available_parts(&self, day: u8) -> u8 {
+        match self.stars.get(&day) {
+            Some(0) => 1,
+            Some(_) => 2,
+            None => 0,
+        }
+    }
+
+    pub fn total(&self) -> u32 {
+        self.stars.values().map(|&stars| u32::from(stars)).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::CALENDAR_PAGE;
+
+    #[test]
+    fn reads_stars_per_day() {
+        let calendar = Calendar::from_page(2024, CALENDAR_PAGE).unwrap();
+        assert_eq!(calendar.stars, BTreeMap::from([(1, 2), (2, 1), (3, 0)]));
+        assert_eq!(calendar.total(), 3);
+        assert!(calendar.is_solved(1, Part::Two));
+        assert!(calendar.is_solved(2, Part::One));
+        assert!(!calendar.is_solved(2, Part::Two));
+        assert_eq!(calendar.stars(25), 0);
+    }
+
+    #[test]
+    fn knows_which_description_parts_exist() {
+        let calendar = Calendar::from_page(2024, CALENDAR_PAGE).unwrap();
+        assert_eq!(calendar.available_parts(2), 2);
+        assert_eq!(calendar.available_parts(3), 1);
+        assert_eq!(calendar.available_parts(4), 0);
+    }
+
+    #[test]
+    fn pages_without_a_calendar_are_rejected() {
+        assert_eq!(Calendar::from_page(2024, "<html>Log in</html>"), None);
+    }
+}
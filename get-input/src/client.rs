@@ -0,0 +1,1055 @@
+use std::collections::hash_map::RandomState;
+use std::env;
+use std::hash::{BuildHasher, Hasher};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use log::{debug, info, trace, warn};
+
+use crate::cache::{read_optional, remove_if_exists, write_file, Cache};
+use crate::calendar::Calendar;
+use crate::description::{description_to_markdown, has_part_two};
+use crate::error::{status_error, FetchError};
+use crate::examples::{extract_examples, Example};
+use crate::leaderboard::{Leaderboard, LEADERBOARD_MAX_AGE};
+use crate::ledger::Ledger;
+use crate::policy::{reserve_slot, RequestPolicy};
+use crate::profile::{Profile, Profiles};
+use crate::puzzle::{format_hms, Part, PuzzleId, BASE_URL};
+use crate::session::{logged_in_user, shared_session, Session, SessionSource, SessionStatus};
+use crate::submit::{article_text, parse_verdict, SubmitError, Verdict};
+use crate::transport::{HttpTransport, Method, Request, Response, Transport};
+use crate::validate::{is_login_page, validate_input};
+
+/// Set to `1` to sleep until a puzzle unlocks instead of failing early.
+pub const WAIT_FOR_UNLOCK_ENV_VAR: &str = "AOC_WAIT_FOR_UNLOCK";
+/// Set to `1` to serve only from the cache and never touch the network.
+pub const OFFLINE_ENV_VAR: &str = "AOC_OFFLINE";
+
+/// Talks to Advent of Code through a `Transport`, caching inputs and recording answers.
+/// The free functions in this crate use `AocClient::new()`; tests point one at a fake.
+#[derive(Debug, Clone)]
+pub struct AocClient<T = HttpTransport> {
+    transport: T,
+    base_url: String,
+    cache: Cache,
+    session: Option<String>,
+    profile: Option<Profile>,
+    profiles: Option<Profiles>,
+    legacy_dir: Option<PathBuf>,
+    description_dir: Option<PathBuf>,
+    wait_for_unlock: bool,
+    offline: bool,
+    clock: fn() -> SystemTime,
+    policy: RequestPolicy,
+}
+
+impl AocClient<HttpTransport> {
+    pub fn new() -> Self {
+        AocClient::with_transport(HttpTransport::default())
+    }
+}
+
+impl Default for AocClient<HttpTransport> {
+    fn default() -> Self {
+        AocClient::new()
+    }
+}
+
+impl<T: Transport> AocClient<T> {
+    /// A client using the environment's cache and session, migrating legacy
+    /// input files from the current directory.
+    pub fn with_transport(transport: T) -> Self {
+        AocClient {
+            transport,
+            base_url: BASE_URL.to_string(),
+            cache: Cache::from_env(),
+            session: None,
+            profile: None,
+            profiles: Profiles::from_env(),
+            legacy_dir: Some(PathBuf::from(".")),
+            description_dir: Some(PathBuf::from(".")),
+            wait_for_unlock: env::var(WAIT_FOR_UNLOCK_ENV_VAR).is_ok_and(|value| value == "1"),
+            offline: env::var(OFFLINE_ENV_VAR).is_ok_and(|value| value == "1"),
+            clock: SystemTime::now,
+            policy: RequestPolicy::default(),
+        }
+    }
+
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn with_cache(mut self, cache: Cache) -> Self {
+        self.cache = cache.with_profile(self.profile.clone());
+        self
+    }
+
+    /// An explicit session token, used instead of the environment, session file or prompt.
+    pub fn with_session(mut self, token: Option<&str>) -> Self {
+        self.session = token.map(str::to_string);
+        self
+    }
+
+    /// Fetches as a named profile: its session file and cache namespace replace the
+    /// default account's, and legacy files, which belong to that account, are not migrated.
+    pub fn with_profile(mut self, profile: Option<Profile>) -> Self {
+        self.cache = self.cache.with_profile(profile.clone());
+        self.profile = profile;
+        self
+    }
+
+    /// Where profile session files are read from.
+    pub fn with_profiles(mut self, profiles: Profiles) -> Self {
+        self.profiles = Some(profiles);
+        self
+    }
+
+    /// Where to look for `input_day_N.txt` files to migrate; `None` disables migration.
+    pub fn with_legacy_dir(mut self, dir: Option<PathBuf>) -> Self {
+        self.legacy_dir = dir;
+        self
+    }
+
+    /// Where to look for the `day-NN` directories descriptions are kept in; `None`
+    /// keeps every description in the cache.
+    pub fn with_description_dir(mut self, dir: Option<PathBuf>) -> Self {
+        self.description_dir = dir;
+        self
+    }
+
+    /// Sleep until a locked puzzle unlocks (plus a little jitter) instead of
+    /// returning `PuzzleNotYetUnlocked`.
+    pub fn with_wait_for_unlock(mut self, wait: bool) -> Self {
+        self.wait_for_unlock = wait;
+        self
+    }
+
+    /// Serve only what is cached: anything else is `NotCached`, without resolving a
+    /// session, so no prompt appears. Stale leaderboards are served as they are.
+    pub fn with_offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// Replaces the clock used to decide whether a puzzle has unlocked.
+    pub fn with_clock(mut self, clock: fn() -> SystemTime) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn with_request_policy(mut self, policy: RequestPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    pub fn cache(&self) -> &Cache {
+        &self.cache
+    }
+
+    /// Returns the cached input, downloading and caching it on a miss.
+    pub async fn fetch_input(&self, id: PuzzleId) -> Result<String, FetchError> {
+        let cache = &self.cache;
+
+        // Pick up inputs saved next to the binaries before the shared cache existed
+        if let (Some(dir), None) = (&self.legacy_dir, &self.profile) {
+            if let Some(legacy_file) = cache
+                .migrate_legacy(id, dir)
+                .map_err(|err| FetchError::io(cache.input_path(id), err))?
+            {
+                info!(
+                    "Migrated {} into {}",
+                    legacy_file.display(),
+                    cache.root().display()
+                );
+            }
+        }
+
+        // Check if the input is already cached
+        if let Some(input) = cache
+            .read_input(id)
+            .map_err(|err| FetchError::io(cache.input_path(id), err))?
+        {
+            info!("Using cached input for {}", id);
+            trace!("Input:\n{}", input);
+            return Ok(input);
+        }
+
+        let path = format!("{}/input", id.path());
+        self.ensure_online(&path)?;
+
+        // Refuse, or wait, rather than ask for a puzzle that has not been released
+        self.wait_until_unlocked(id).await?;
+
+        info!("Fetching input for {}", id);
+        let input = self.get(Some(id), &path).await?;
+
+        // Never cache an error page as if it were the input
+        validate_input(&input).map_err(|reason| FetchError::InvalidContent {
+            url: format!("{}{}", self.base_url, path),
+            reason,
+        })?;
+
+        // Log the input and save it to the cache
+        trace!("Input:\n{}", input);
+        cache
+            .write_input(id, &input)
+            .map_err(|err| FetchError::io(cache.input_path(id), err))?;
+        Ok(input)
+    }
+
+    /// Downloads every unlocked day of `year` that is not cached yet, e.g. before a
+    /// flight, and returns the days that were downloaded. Stops at the first failure;
+    /// running it again picks up where it left off.
+    pub async fn prefetch(&self, year: u16) -> Result<Vec<PuzzleId>, FetchError> {
+        let mut fetched = Vec::new();
+        for day in 1..=25 {
+            let id = PuzzleId::new(year, day)?;
+            if id.time_until_unlock((self.clock)()).is_some() {
+                break;
+            }
+            if !self.cache.has_input(id) {
+                self.fetch_input(id).await?;
+                fetched.push(id);
+            }
+        }
+        Ok(fetched)
+    }
+
+    /// Where the description of `id` is kept: `day-NN/puzzle.md` when that day's
+    /// directory exists, otherwise next to the cached input.
+    pub fn description_path(&self, id: PuzzleId) -> PathBuf {
+        match &self.description_dir {
+            Some(dir) if dir.join(format!("day-{:02}", id.day())).is_dir() => {
+                dir.join(format!("day-{:02}", id.day())).join("puzzle.md")
+            }
+            _ => self.cache.description_path(id),
+        }
+    }
+
+    /// Returns the puzzle description as Markdown, saved to `description_path`. Part
+    /// two is included once part one has been solved: a saved copy without it is
+    /// fetched again when the calendar shows a star for the day.
+    pub async fn fetch_description(&self, id: PuzzleId) -> Result<String, FetchError> {
+        let path = self.description_path(id);
+        if let Some(markdown) = read_optional(&path).map_err(|err| FetchError::io(&path, err))? {
+            if has_part_two(&markdown) || self.offline || !self.part_two_unlocked(id).await {
+                info!("Using cached description for {}", id);
+                return Ok(markdown);
+            }
+            info!("Cached description for {} lacks part two", id);
+        }
+
+        info!("Fetching description for {}", id);
+        let page = self.fetch_page(id).await?;
+        let markdown = description_to_markdown(&page, &self.base_url).ok_or_else(|| {
+            FetchError::UnexpectedPage {
+                url: format!("{}{}", self.base_url, id.path()),
+                expected: "a puzzle description",
+            }
+        })?;
+
+        write_file(path.clone(), &markdown).map_err(|err| FetchError::io(&path, err))?;
+        Ok(markdown)
+    }
+
+    // Whether the calendar shows part one solved; a calendar that cannot be read
+    // leaves the saved description in use
+    async fn part_two_unlocked(&self, id: PuzzleId) -> bool {
+        match self.fetch_calendar(id.year()).await {
+            Ok(calendar) => calendar.available_parts(id.day()) == 2,
+            Err(err) => {
+                warn!("Could not read the {} calendar: {}", id.year(), err);
+                false
+            }
+        }
+    }
+
+    /// Extracts the example inputs and answers from the puzzle page. Not cached, since
+    /// the answers for part two appear once part one is solved.
+    pub async fn fetch_examples(&self, id: PuzzleId) -> Result<Vec<Example>, FetchError> {
+        info!("Fetching examples for {}", id);
+        let page = self.fetch_page(id).await?;
+        Ok(extract_examples(&page))
+    }
+
+    async fn fetch_page(&self, id: PuzzleId) -> Result<String, FetchError> {
+        self.ensure_online(&id.path())?;
+        self.wait_until_unlocked(id).await?;
+        self.get(Some(id), &id.path()).await
+    }
+
+    /// Returns a private leaderboard, downloading it at most once per
+    /// `LEADERBOARD_MAX_AGE` and serving the cached copy in between.
+    pub async fn fetch_leaderboard(
+        &self,
+        year: u16,
+        leaderboard: u64,
+    ) -> Result<Leaderboard, FetchError> {
+        let cache = &self.cache;
+        let path = cache.leaderboard_path(year, leaderboard);
+        let cached = cache
+            .read_leaderboard(year, leaderboard)
+            .map_err(|err| FetchError::io(&path, err))?;
+        if let Some((json, written)) = cached {
+            let age = (self.clock)().duration_since(written).unwrap_or_default();
+            if age < LEADERBOARD_MAX_AGE || self.offline {
+                if let Ok(parsed) = Leaderboard::from_json(&json) {
+                    info!(
+                        "Using leaderboard {} cached {} ago",
+                        leaderboard,
+                        format_hms(age)
+                    );
+                    return Ok(parsed);
+                }
+            }
+        }
+
+        let url_path = format!("/{}/leaderboard/private/view/{}.json", year, leaderboard);
+        let json = self.get(None, &url_path).await?;
+        // Non-members are redirected to an HTML page, which must not be cached
+        let parsed = Leaderboard::from_json(&json).map_err(|_| FetchError::UnexpectedPage {
+            url: format!("{}{}", self.base_url, url_path),
+            expected: "leaderboard JSON",
+        })?;
+        cache
+            .write_leaderboard(year, leaderboard, &json)
+            .map_err(|err| FetchError::io(&path, err))?;
+        Ok(parsed)
+    }
+
+    /// Checks the session against the cheap `/settings` page, reporting the
+    /// logged-in user or that the token has expired.
+    pub async fn validate_session(&self) -> Result<SessionStatus, FetchError> {
+        self.ensure_online("/settings")?;
+        let source = self.resolve_session().await?.source().clone();
+        match self.get(None, "/settings").await {
+            Ok(page) => Ok(match logged_in_user(&page) {
+                Some(user) => SessionStatus::Valid { user, source },
+                None => SessionStatus::Expired { source },
+            }),
+            Err(FetchError::ExpiredSession { source }) => Ok(SessionStatus::Expired { source }),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Reads the stars earned so far from the year's calendar page. Not cached,
+    /// since it changes with every solve.
+    pub async fn fetch_calendar(&self, year: u16) -> Result<Calendar, FetchError> {
+        let path = format!("/{}", year);
+        let page = self.get(None, &path).await?;
+        Calendar::from_page(year, &page).ok_or_else(|| FetchError::UnexpectedPage {
+            url: format!("{}{}", self.base_url, path),
+            expected: "a calendar",
+        })
+    }
+
+    /// Submits an answer for one part of a puzzle and reports the verdict.
+    /// Answers the ledger already knows to be wrong are refused without a request,
+    /// and every verdict is recorded in the ledger.
+    pub async fn submit_answer(
+        &self,
+        id: PuzzleId,
+        part: Part,
+        answer: &str,
+    ) -> Result<Verdict, SubmitError> {
+        let mut ledger = Ledger::load(self.cache.ledger_path(id))?;
+        ledger.check(part, answer).map_err(SubmitError::Refused)?;
+        self.ensure_online(&format!("{}/answer", id.path()))?;
+
+        let session = self.resolve_session().await?;
+        let url = format!("{}{}/answer", self.base_url, id.path());
+        let level = part.level().to_string();
+        let request = Request::post(
+            &url,
+            session.token(),
+            &[("level", &level), ("answer", answer.trim())],
+        );
+
+        let response = self.send(request).await?;
+        if !response.status.is_success() {
+            return Err(status_error(&url, response.status, Some(id), session.source()).into());
+        }
+        if is_login_page(&response.body) {
+            let source = session.source().clone();
+            return Err(FetchError::ExpiredSession { source }.into());
+        }
+
+        let verdict = parse_verdict(&response.body)
+            .ok_or_else(|| SubmitError::UnrecognizedResponse(article_text(&response.body)))?;
+        ledger.record(part, answer, verdict)?;
+
+        // Solving part one unlocks the part two text, so the cached description is stale
+        if verdict == Verdict::Correct && part == Part::One {
+            remove_if_exists(&self.description_path(id))?;
+        }
+        Ok(verdict)
+    }
+
+    // Sends an authenticated GET for a site-relative path and returns the body of a success
+    async fn get(&self, id: Option<PuzzleId>, path: &str) -> Result<String, FetchError> {
+        self.ensure_online(path)?;
+        let session = self.resolve_session().await?;
+        debug!("Using session token from {}", session.source());
+
+        let url = format!("{}{}", self.base_url, path);
+        let response = self.send(Request::get(&url, session.token())).await?;
+
+        if !response.status.is_success() {
+            warn!("Failed to fetch {}. Status code: {}", url, response.status);
+            Err(status_error(&url, response.status, id, session.source()))
+        } else if is_login_page(&response.body) {
+            // An expired cookie on an HTML page is redirected to the login page
+            Err(FetchError::ExpiredSession {
+                source: session.source().clone(),
+            })
+        } else {
+            Ok(response.body)
+        }
+    }
+
+    // Checked before a session is resolved, since that may prompt
+    fn ensure_online(&self, path: &str) -> Result<(), FetchError> {
+        if self.offline {
+            Err(FetchError::NotCached {
+                url: format!("{}{}", self.base_url, path),
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    // Sends through the transport under the request policy. Only GETs are retried:
+    // a submission that failed mid-flight may still have been counted.
+    async fn send(&self, request: Request) -> Result<Response, FetchError> {
+        let retries = match request.method {
+            Method::Get => self.policy.max_retries,
+            Method::Post => 0,
+        };
+
+        let mut attempt = 0;
+        loop {
+            self.throttle().await?;
+            let result = self.transport.send(request.clone()).await;
+            let retryable = match &result {
+                Ok(response) => response.status.is_server_error(),
+                Err(err) => matches!(err, FetchError::Network { .. }),
+            };
+            if !retryable || attempt >= retries {
+                return result;
+            }
+
+            let delay = self.policy.backoff(attempt);
+            warn!(
+                "Request to {} failed, retrying in {}s",
+                request.url,
+                delay.as_secs_f32()
+            );
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    // Waits for this process's turn under the minimum interval shared through the cache
+    async fn throttle(&self) -> Result<(), FetchError> {
+        let interval = self.policy.min_interval;
+        if interval.is_zero() {
+            return Ok(());
+        }
+
+        let path = self.cache.throttle_path();
+        let slot_path = path.clone();
+        let wait = tokio::task::spawn_blocking(move || {
+            reserve_slot(&slot_path, interval, SystemTime::now())
+        })
+        .await
+        .map_err(FetchError::TaskFailed)?
+        .map_err(|err| FetchError::io(&path, err))?;
+
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+        Ok(())
+    }
+
+    async fn wait_until_unlocked(&self, id: PuzzleId) -> Result<(), FetchError> {
+        let remaining = match id.time_until_unlock((self.clock)()) {
+            Some(remaining) => remaining,
+            None => return Ok(()),
+        };
+        if !self.wait_for_unlock {
+            return Err(FetchError::PuzzleNotYetUnlocked {
+                id,
+                remaining: Some(remaining),
+            });
+        }
+
+        info!("{} unlocks in {}, waiting", id, format_hms(remaining));
+        tokio::time::sleep(remaining + unlock_jitter()).await;
+        Ok(())
+    }
+
+    async fn resolve_session(&self) -> Result<Session, FetchError> {
+        match &self.session {
+            Some(token) if !token.trim().is_empty() => {
+                Ok(Session::new(token, SessionSource::Argument))
+            }
+            _ => match (&self.profile, &self.profiles) {
+                (Some(profile), Some(profiles)) => profiles.session(profile),
+                (Some(profile), None) => Err(FetchError::MissingProfile {
+                    profile: profile.clone(),
+                    session_file: None,
+                }),
+                (None, _) => shared_session(None).await,
+            },
+        }
+    }
+}
+
+// One to five seconds, so scripted fetches do not all land on the unlock instant
+fn unlock_jitter() -> Duration {
+    let random = RandomState::new().build_hasher().finish();
+    Duration::from_millis(1000 + random % 4000)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{FakeTransport, CALENDAR_PAGE, INPUT_PAGE, LEADERBOARD_JSON};
+    use crate::validate::InvalidInput;
+    use reqwest::StatusCode;
+
+    fn client(fake: &FakeTransport, cache: &tempfile::TempDir) -> AocClient<FakeTransport> {
+        AocClient::with_transport(fake.clone())
+            .with_base_url("https://aoc.test")
+            .with_cache(Cache::new(cache.path()))
+            .with_session(Some("cookie"))
+            .with_legacy_dir(None)
+            .with_description_dir(None)
+            .with_request_policy(RequestPolicy {
+                min_interval: Duration::ZERO,
+                max_retries: 2,
+                initial_backoff: Duration::ZERO,
+                max_backoff: Duration::ZERO,
+            })
+    }
+
+    fn day(day: u8) -> PuzzleId {
+        PuzzleId::new(2024, day).unwrap()
+    }
+
+    #[tokio::test]
+    async fn cache_miss_downloads_and_caches() {
+        let dir = tempfile::tempdir().unwrap();
+        let fake = FakeTransport::new().route("/2024/day/1/input", StatusCode::OK, INPUT_PAGE);
+        let client = client(&fake, &dir);
+
+        assert_eq!(client.fetch_input(day(1)).await.unwrap(), INPUT_PAGE);
+        assert_eq!(
+            client.cache().read_input(day(1)).unwrap().as_deref(),
+            Some(INPUT_PAGE)
+        );
+
+        let requests = fake.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, Method::Get);
+        assert_eq!(requests[0].url, "https://aoc.test/2024/day/1/input");
+        assert_eq!(requests[0].session, "cookie");
+    }
+
+    #[tokio::test]
+    async fn cache_hit_skips_the_network() {
+        let dir = tempfile::tempdir().unwrap();
+        let fake = FakeTransport::new();
+        let client = client(&fake, &dir);
+        client.cache().write_input(day(2), "7 6 4 2 1\n").unwrap();
+
+        assert_eq!(client.fetch_input(day(2)).await.unwrap(), "7 6 4 2 1\n");
+        assert!(fake.requests().is_empty());
+    }
+
+    #[tokio::test]
+    async fn profiles_use_their_own_session_and_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = tempfile::tempdir().unwrap();
+        std::fs::write(config.path().join("alice"), "alice-cookie\n").unwrap();
+        let work = tempfile::tempdir().unwrap();
+        std::fs::write(work.path().join("input_day_1.txt"), "not alice's").unwrap();
+        let fake = FakeTransport::new().route("/2024/day/1/input", StatusCode::OK, INPUT_PAGE);
+        let default = client(&fake, &dir);
+        default.cache().write_input(day(1), "3   4\n").unwrap();
+
+        let alice = client(&fake, &dir)
+            .with_session(None)
+            .with_profiles(Profiles::new(config.path()))
+            .with_legacy_dir(Some(work.path().to_path_buf()))
+            .with_profile(Some(Profile::new("alice").unwrap()));
+        assert_eq!(alice.fetch_input(day(1)).await.unwrap(), INPUT_PAGE);
+        assert_eq!(fake.requests()[0].session, "alice-cookie");
+        assert!(alice
+            .cache()
+            .input_path(day(1))
+            .starts_with(dir.path().join("profiles")));
+        assert_eq!(
+            default.cache().read_input(day(1)).unwrap().as_deref(),
+            Some("3   4\n")
+        );
+
+        let bob = alice.with_profile(Some(Profile::new("bob").unwrap()));
+        let err = bob.fetch_input(day(1)).await.unwrap_err();
+        assert!(
+            matches!(err, FetchError::MissingProfile { .. }),
+            "{:?}",
+            err
+        );
+        assert_eq!(fake.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn legacy_files_are_migrated_before_fetching() {
+        let dir = tempfile::tempdir().unwrap();
+        let work = tempfile::tempdir().unwrap();
+        std::fs::write(work.path().join("input_day_3.txt"), "mul(2,4)").unwrap();
+        let fake = FakeTransport::new();
+        let client = client(&fake, &dir).with_legacy_dir(Some(work.path().to_path_buf()));
+
+        assert_eq!(client.fetch_input(day(3)).await.unwrap(), "mul(2,4)");
+        assert!(fake.requests().is_empty());
+    }
+
+    #[tokio::test]
+    async fn login_page_means_expired_session() {
+        let dir = tempfile::tempdir().unwrap();
+        let fake = FakeTransport::new().route(
+            "/2024/day/4/input",
+            StatusCode::BAD_REQUEST,
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+        );
+        let client = client(&fake, &dir);
+
+        let result = client.fetch_input(day(4)).await;
+        assert!(matches!(
+            result,
+            Err(FetchError::ExpiredSession {
+                source: SessionSource::Argument
+            })
+        ));
+        assert_eq!(client.cache().read_input(day(4)).unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn missing_page_means_not_unlocked() {
+        let dir = tempfile::tempdir().unwrap();
+        let fake = FakeTransport::new().route(
+            "/2024/day/25/input",
+            StatusCode::NOT_FOUND,
+            "Please don't repeatedly request this endpoint before it unlocks!",
+        );
+        let client = client(&fake, &dir);
+
+        let result = client.fetch_input(day(25)).await;
+        assert!(matches!(
+            result,
+            Err(FetchError::PuzzleNotYetUnlocked { id, remaining: None }) if id == day(25)
+        ));
+    }
+
+    #[tokio::test]
+    async fn locked_puzzles_are_refused_before_any_request() {
+        let dir = tempfile::tempdir().unwrap();
+        let fake = FakeTransport::new();
+        // An hour and a minute before 2024 day 25 unlocks
+        let client = client(&fake, &dir).with_clock(|| {
+            PuzzleId::new(2024, 25).unwrap().unlock_time() - Duration::from_secs(3660)
+        });
+
+        let result = client.fetch_input(day(25)).await;
+        let err = result.unwrap_err();
+        assert_eq!(err.to_string(), "2024 day 25 unlocks in 01:01:00");
+        assert!(matches!(
+            err,
+            FetchError::PuzzleNotYetUnlocked { remaining: Some(remaining), .. }
+                if remaining == Duration::from_secs(3660)
+        ));
+        assert!(fake.requests().is_empty());
+    }
+
+    const PUZZLE_PAGE: &str = r#"<html><body><main><article class="day-desc"><h2>--- Day 7: Bridge Repair ---</h2><p>Find the <em>total</em>.</p></article><article class="day-desc"><h2>--- Part Two ---</h2><p>Add <code>||</code>.</p></article></main></body></html>"#;
+    const PUZZLE_MARKDOWN: &str =
+        "## --- Day 7: Bridge Repair ---\n\nFind the *total*.\n\n## --- Part Two ---\n\nAdd `||`.\n";
+
+    #[tokio::test]
+    async fn descriptions_are_converted_and_cached() {
+        let dir = tempfile::tempdir().unwrap();
+        let fake = FakeTransport::new().route("/2024/day/7", StatusCode::OK, PUZZLE_PAGE);
+        let client = client(&fake, &dir);
+
+        assert_eq!(
+            client.fetch_description(day(7)).await.unwrap(),
+            PUZZLE_MARKDOWN
+        );
+        assert_eq!(
+            client.fetch_description(day(7)).await.unwrap(),
+            PUZZLE_MARKDOWN
+        );
+        assert_eq!(fake.requests().len(), 1);
+        assert!(client.cache().description_path(day(7)).exists());
+    }
+
+    #[tokio::test]
+    async fn descriptions_are_kept_in_day_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        let work = tempfile::tempdir().unwrap();
+        std::fs::create_dir(work.path().join("day-07")).unwrap();
+        let fake = FakeTransport::new()
+            .route("/2024/day/7", StatusCode::OK, PUZZLE_PAGE)
+            .route("/2024/day/8", StatusCode::OK, PUZZLE_PAGE);
+        let client = client(&fake, &dir).with_description_dir(Some(work.path().to_path_buf()));
+
+        client.fetch_description(day(7)).await.unwrap();
+        let path = work.path().join("day-07/puzzle.md");
+        assert_eq!(client.description_path(day(7)), path);
+        assert_eq!(std::fs::read_to_string(path).unwrap(), PUZZLE_MARKDOWN);
+        assert!(!client.cache().description_path(day(7)).exists());
+
+        // Without a day directory the description goes to the cache
+        client.fetch_description(day(8)).await.unwrap();
+        assert!(client.cache().description_path(day(8)).exists());
+    }
+
+    #[tokio::test]
+    async fn descriptions_without_part_two_are_refetched_once_part_one_is_solved() {
+        let dir = tempfile::tempdir().unwrap();
+        // Day 2 has one star on the calendar, day 3 none
+        let fake = FakeTransport::new()
+            .route("/2024", StatusCode::OK, CALENDAR_PAGE)
+            .route("/2024/day/2", StatusCode::OK, PUZZLE_PAGE);
+        let client = client(&fake, &dir);
+        for id in [day(2), day(3)] {
+            std::fs::create_dir_all(client.cache().description_path(id).parent().unwrap()).unwrap();
+            std::fs::write(client.cache().description_path(id), "part one only\n").unwrap();
+        }
+
+        assert_eq!(
+            client.fetch_description(day(2)).await.unwrap(),
+            PUZZLE_MARKDOWN
+        );
+        assert_eq!(
+            client.fetch_description(day(3)).await.unwrap(),
+            "part one only\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(client.cache().description_path(day(2))).unwrap(),
+            PUZZLE_MARKDOWN
+        );
+    }
+
+    #[tokio::test]
+    async fn solving_part_one_invalidates_the_description() {
+        let dir = tempfile::tempdir().unwrap();
+        let fake = FakeTransport::new().route(
+            "/2024/day/7/answer",
+            StatusCode::OK,
+            "<article><p>That's the right answer!</p></article>",
+        );
+        let client = client(&fake, &dir);
+        let path = client.description_path(day(7));
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "part one only").unwrap();
+
+        client
+            .submit_answer(day(7), Part::One, "3749")
+            .await
+            .unwrap();
+        assert!(!path.exists());
+    }
+
+    #[tokio::test]
+    async fn pages_without_puzzle_text_are_unexpected() {
+        let dir = tempfile::tempdir().unwrap();
+        let fake = FakeTransport::new().route("/2024/day/7", StatusCode::OK, "<html></html>");
+        let client = client(&fake, &dir);
+
+        let result = client.fetch_description(day(7)).await;
+        assert!(matches!(result, Err(FetchError::UnexpectedPage { .. })));
+    }
+
+    #[tokio::test]
+    async fn examples_come_from_the_puzzle_page() {
+        let dir = tempfile::tempdir().unwrap();
+        let page = r#"<article class="day-desc"><pre><code>1 2
+</code></pre><p><code><em>3</em></code></p></article>"#;
+        let fake = FakeTransport::new().route("/2024/day/7", StatusCode::OK, page);
+        let client = client(&fake, &dir);
+
+        let examples = client.fetch_examples(day(7)).await.unwrap();
+        assert_eq!(examples.len(), 1);
+        assert_eq!(examples[0].input, "1 2\n");
+        assert_eq!(examples[0].part1.as_deref(), Some("3"));
+    }
+
+    fn sixteen_minutes_later() -> SystemTime {
+        SystemTime::now() + Duration::from_secs(16 * 60)
+    }
+
+    #[tokio::test]
+    async fn leaderboards_are_polled_at_most_every_fifteen_minutes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = "/2024/leaderboard/private/view/1.json";
+        let fake = FakeTransport::new().route(path, StatusCode::OK, LEADERBOARD_JSON);
+        let client = client(&fake, &dir);
+
+        let leaderboard = client.fetch_leaderboard(2024, 1).await.unwrap();
+        assert_eq!(leaderboard.members.len(), 3);
+        client.fetch_leaderboard(2024, 1).await.unwrap();
+        assert_eq!(fake.requests().len(), 1);
+
+        let later = client.with_clock(sixteen_minutes_later);
+        later.fetch_leaderboard(2024, 1).await.unwrap();
+        assert_eq!(fake.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn offline_clients_only_serve_the_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = "/2024/leaderboard/private/view/1.json";
+        let fake = FakeTransport::new()
+            .route("/2024/day/1/input", StatusCode::OK, INPUT_PAGE)
+            .route(path, StatusCode::OK, LEADERBOARD_JSON);
+        client(&fake, &dir)
+            .fetch_leaderboard(2024, 1)
+            .await
+            .unwrap();
+        let offline = client(&fake, &dir)
+            .with_session(None)
+            .with_offline(true)
+            .with_clock(sixteen_minutes_later);
+        offline.cache().write_input(day(2), "7 6 4 2 1\n").unwrap();
+
+        assert_eq!(offline.fetch_input(day(2)).await.unwrap(), "7 6 4 2 1\n");
+        let err = offline.fetch_input(day(1)).await.unwrap_err();
+        assert!(
+            matches!(&err, FetchError::NotCached { url } if url == "https://aoc.test/2024/day/1/input"),
+            "{:?}",
+            err
+        );
+        assert!(offline.fetch_leaderboard(2024, 1).await.is_ok());
+        assert!(matches!(
+            offline.fetch_calendar(2024).await,
+            Err(FetchError::NotCached { .. })
+        ));
+        assert_eq!(fake.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn prefetch_downloads_unlocked_days_that_are_not_cached() {
+        let dir = tempfile::tempdir().unwrap();
+        let fake = FakeTransport::new()
+            .route("/2024/day/1/input", StatusCode::OK, INPUT_PAGE)
+            .route("/2024/day/3/input", StatusCode::OK, "xmul(2,4)\n");
+        // Just after day 3 unlocks
+        let client = client(&fake, &dir)
+            .with_clock(|| PuzzleId::new(2024, 3).unwrap().unlock_time() + Duration::from_secs(60));
+        client.cache().write_input(day(2), "7 6 4 2 1\n").unwrap();
+
+        assert_eq!(client.prefetch(2024).await.unwrap(), [day(1), day(3)]);
+        assert_eq!(fake.requests().len(), 2);
+        assert!(client.prefetch(2024).await.unwrap().is_empty());
+        assert_eq!(fake.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn leaderboard_redirects_are_not_cached() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = "/2024/leaderboard/private/view/1.json";
+        let fake = FakeTransport::new().route(path, StatusCode::OK, "<html>Log in</html>");
+        let client = client(&fake, &dir);
+
+        let result = client.fetch_leaderboard(2024, 1).await;
+        assert!(matches!(result, Err(FetchError::UnexpectedPage { .. })));
+        assert!(!client.cache().leaderboard_path(2024, 1).exists());
+    }
+
+    const LOGIN_PAGE: &str = "<html><body><main><article><p>To play, please identify yourself via one of these services:</p></article></main></body></html>";
+
+    #[tokio::test]
+    async fn validate_session_reports_the_user() {
+        let dir = tempfile::tempdir().unwrap();
+        let page = r#"<html><header><div class="user">Ada <span class="star-count">3*</span></div></header></html>"#;
+        let fake = FakeTransport::new().route("/settings", StatusCode::OK, page);
+        let client = client(&fake, &dir);
+
+        assert_eq!(
+            client.validate_session().await.unwrap(),
+            SessionStatus::Valid {
+                user: "Ada".to_string(),
+                source: SessionSource::Argument
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn validate_session_reports_expiry() {
+        let dir = tempfile::tempdir().unwrap();
+        let fake = FakeTransport::new().route("/settings", StatusCode::OK, LOGIN_PAGE);
+        let client = client(&fake, &dir);
+
+        assert_eq!(
+            client.validate_session().await.unwrap(),
+            SessionStatus::Expired {
+                source: SessionSource::Argument
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn login_redirects_are_expired_sessions() {
+        let dir = tempfile::tempdir().unwrap();
+        let fake = FakeTransport::new().route("/2024/day/7", StatusCode::OK, LOGIN_PAGE);
+        let client = client(&fake, &dir);
+
+        let err = client.fetch_description(day(7)).await.unwrap_err();
+        assert!(matches!(err, FetchError::ExpiredSession { .. }));
+        assert!(err.to_string().contains("pass the new token instead"));
+    }
+
+    #[tokio::test]
+    async fn calendar_comes_from_the_year_page() {
+        let dir = tempfile::tempdir().unwrap();
+        let fake = FakeTransport::new().route("/2024", StatusCode::OK, CALENDAR_PAGE);
+        let client = client(&fake, &dir);
+
+        let calendar = client.fetch_calendar(2024).await.unwrap();
+        assert_eq!(calendar.total(), 3);
+        assert!(matches!(
+            client.fetch_calendar(2023).await,
+            Err(FetchError::Network { .. })
+        ));
+    }
+
+    #[test]
+    fn jitter_is_a_few_seconds() {
+        for _ in 0..100 {
+            let jitter = unlock_jitter();
+            assert!(jitter >= Duration::from_secs(1) && jitter < Duration::from_secs(5));
+        }
+    }
+
+    #[tokio::test]
+    async fn server_errors_are_http_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let fake =
+            FakeTransport::new().route("/2024/day/5/input", StatusCode::SERVICE_UNAVAILABLE, "");
+        let client = client(&fake, &dir);
+
+        let result = client.fetch_input(day(5)).await;
+        assert!(matches!(
+            result,
+            Err(FetchError::Http { status: StatusCode::SERVICE_UNAVAILABLE, url })
+                if url == "https://aoc.test/2024/day/5/input"
+        ));
+        assert_eq!(client.cache().read_input(day(5)).unwrap(), None);
+        assert_eq!(fake.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn server_errors_are_retried_with_backoff() {
+        let dir = tempfile::tempdir().unwrap();
+        let fake = FakeTransport::new()
+            .route("/2024/day/5/input", StatusCode::BAD_GATEWAY, "")
+            .route("/2024/day/5/input", StatusCode::OK, INPUT_PAGE);
+        let client = client(&fake, &dir);
+
+        assert_eq!(client.fetch_input(day(5)).await.unwrap(), INPUT_PAGE);
+        assert_eq!(fake.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn client_errors_and_submissions_are_not_retried() {
+        let dir = tempfile::tempdir().unwrap();
+        let fake = FakeTransport::new()
+            .route("/2024/day/5/input", StatusCode::NOT_FOUND, "")
+            .route("/2024/day/5/answer", StatusCode::INTERNAL_SERVER_ERROR, "");
+        let client = client(&fake, &dir);
+
+        assert!(client.fetch_input(day(5)).await.is_err());
+        assert!(client.submit_answer(day(5), Part::One, "1").await.is_err());
+        assert_eq!(fake.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn requests_are_spaced_by_the_minimum_interval() {
+        let dir = tempfile::tempdir().unwrap();
+        let fake = FakeTransport::new().route("/2024", StatusCode::OK, CALENDAR_PAGE);
+        let client = client(&fake, &dir).with_request_policy(RequestPolicy {
+            min_interval: Duration::from_millis(200),
+            ..RequestPolicy::default()
+        });
+
+        let start = std::time::Instant::now();
+        client.fetch_calendar(2024).await.unwrap();
+        client.fetch_calendar(2024).await.unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(200));
+        assert!(client.cache().throttle_path().exists());
+    }
+
+    #[tokio::test]
+    async fn error_pages_with_success_status_are_not_cached() {
+        let dir = tempfile::tempdir().unwrap();
+        let fake = FakeTransport::new().route(
+            "/2024/day/8/input",
+            StatusCode::OK,
+            "<!DOCTYPE html>\n<html><body><main>Advent of Code</main></body></html>",
+        );
+        let client = client(&fake, &dir);
+
+        let result = client.fetch_input(day(8)).await;
+        assert!(matches!(
+            result,
+            Err(FetchError::InvalidContent {
+                reason: InvalidInput::Html,
+                ..
+            })
+        ));
+        assert_eq!(client.cache().read_input(day(8)).unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn unrouted_paths_are_network_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let fake = FakeTransport::new();
+        let client = client(&fake, &dir);
+
+        let result = client.fetch_input(day(6)).await;
+        assert!(matches!(result, Err(FetchError::Network { .. })));
+    }
+
+    #[tokio::test]
+    async fn submission_is_recorded_in_the_ledger() {
+        let dir = tempfile::tempdir().unwrap();
+        let fake = FakeTransport::new().respond(
+            "/2024/day/7/answer",
+            Response::new(
+                StatusCode::OK,
+                "<article><p>That's not the right answer; your answer is too low.</p></article>",
+            ),
+        );
+        let client = client(&fake, &dir);
+
+        let verdict = client.submit_answer(day(7), Part::One, "100").await;
+        assert_eq!(verdict.unwrap(), Verdict::TooLow);
+        assert_eq!(
+            fake.requests()[0].form,
+            vec![
+                ("level".to_string(), "1".to_string()),
+                ("answer".to_string(), "100".to_string())
+            ]
+        );
+
+        // Anything at or below a known "too low" answer never reaches the site
+        let refused = client.submit_answer(day(7), Part::One, "90").await;
+        assert!(matches!(refused, Err(SubmitError::Refused(_))));
+        assert_eq!(fake.requests().len(), 1);
+    }
+}
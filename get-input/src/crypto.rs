@@ -0,0 +1,247 @@
+//! Encryption at rest for cached inputs: PBKDF2-HMAC-SHA256 derives a key from a
+//! passphrase or key file, and ChaCha20-Poly1305 seals each input.
+
+use std::env;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::num::NonZeroU32;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, CHACHA20_POLY1305, NONCE_LEN};
+use ring::pbkdf2;
+use ring::rand::{SecureRandom, SystemRandom};
+
+/// Passphrase for the encrypted cache.
+pub const PASSPHRASE_ENV_VAR: &str = "AOC_CACHE_PASSPHRASE";
+/// File whose contents are the secret for the encrypted cache; wins over a passphrase.
+pub const KEY_FILE_ENV_VAR: &str = "AOC_CACHE_KEY_FILE";
+
+const MAGIC: &[u8] = b"AOC1";
+const SALT_LEN: usize = 16;
+const KEY_LEN: usize = 32;
+// OWASP's recommendation for PBKDF2-HMAC-SHA256; tests derive many keys, cheaply
+const PBKDF2_ITERATIONS: u32 = if cfg!(test) { 1_000 } else { 600_000 };
+
+/// Where the secret for the encrypted cache comes from.
+#[derive(Clone, PartialEq, Eq)]
+pub enum KeySource {
+    Passphrase(String),
+    KeyFile(PathBuf),
+}
+
+impl KeySource {
+    /// `AOC_CACHE_KEY_FILE`, then `AOC_CACHE_PASSPHRASE`; `None` leaves the cache in plaintext.
+    pub fn from_env() -> Option<Self> {
+        let non_empty = |name| env::var_os(name).filter(|value| !value.is_empty());
+        if let Some(path) = non_empty(KEY_FILE_ENV_VAR) {
+            return Some(KeySource::KeyFile(PathBuf::from(path)));
+        }
+        non_empty(PASSPHRASE_ENV_VAR)
+            .map(|passphrase| KeySource::Passphrase(passphrase.to_string_lossy().into_owned()))
+    }
+
+    fn secret(&self) -> io::Result<Vec<u8>> {
+        match self {
+            KeySource::Passphrase(passphrase) => Ok(passphrase.as_bytes().to_vec()),
+            KeySource::KeyFile(path) => fs::read(path).map_err(|err| {
+                io::Error::new(
+                    err.kind(),
+                    format!("reading key file {}: {}", path.display(), err),
+                )
+            }),
+        }
+    }
+}
+
+// Never print the passphrase
+impl fmt::Debug for KeySource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeySource::Passphrase(_) => f.write_str("Passphrase(<redacted>)"),
+            KeySource::KeyFile(path) => f.debug_tuple("KeyFile").field(path).finish(),
+        }
+    }
+}
+
+/// A key derived for one cache, which keeps its salt in `salt_path`.
+pub(crate) struct CacheKey(LessSafeKey);
+
+impl CacheKey {
+    /// Derives the key, creating a random salt on first use. Losing the salt file
+    /// makes the encrypted inputs unreadable, though they can always be downloaded again.
+    pub fn derive(source: &KeySource, salt_path: &Path) -> io::Result<Self> {
+        let salt = match fs::read(salt_path) {
+            Ok(salt) => salt,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => create_salt(salt_path)?,
+            Err(err) => return Err(err),
+        };
+
+        let mut key = [0; KEY_LEN];
+        let iterations = NonZeroU32::new(PBKDF2_ITERATIONS).unwrap();
+        pbkdf2::derive(
+            pbkdf2::PBKDF2_HMAC_SHA256,
+            iterations,
+            &salt,
+            &source.secret()?,
+            &mut key,
+        );
+        let key = UnboundKey::new(&CHACHA20_POLY1305, &key).map_err(|_| crypto_error())?;
+        Ok(CacheKey(LessSafeKey::new(key)))
+    }
+
+    /// Encrypts `plaintext` bound to `context`, which must be given again to open it.
+    pub fn seal(&self, context: &str, plaintext: &[u8]) -> io::Result<Vec<u8>> {
+        let mut nonce = [0; NONCE_LEN];
+        fill_random(&mut nonce)?;
+
+        let mut sealed = plaintext.to_vec();
+        self.0
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(context.as_bytes()),
+                &mut sealed,
+            )
+            .map_err(|_| crypto_error())?;
+
+        let mut file = Vec::with_capacity(MAGIC.len() + NONCE_LEN + sealed.len());
+        file.extend_from_slice(MAGIC);
+        file.extend_from_slice(&nonce);
+        file.extend_from_slice(&sealed);
+        Ok(file)
+    }
+
+    pub fn open(&self, context: &str, file: &[u8]) -> io::Result<Vec<u8>> {
+        let body = file
+            .strip_prefix(MAGIC)
+            .filter(|body| body.len() >= NONCE_LEN)
+            .ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidData, "not an encrypted cache file")
+            })?;
+        let (nonce, sealed) = body.split_at(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(nonce).map_err(|_| crypto_error())?;
+
+        let mut sealed = sealed.to_vec();
+        let plaintext = self
+            .0
+            .open_in_place(nonce, Aad::from(context.as_bytes()), &mut sealed)
+            .map_err(|_| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "cannot decrypt: wrong passphrase or key file, or a corrupted file",
+                )
+            })?;
+        Ok(plaintext.to_vec())
+    }
+}
+
+impl fmt::Debug for CacheKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("CacheKey(<redacted>)")
+    }
+}
+
+// Only one process may create the salt, or inputs sealed by the others become
+// unreadable: `create_new` picks the winner and everyone else reads its salt
+fn create_salt(salt_path: &Path) -> io::Result<Vec<u8>> {
+    if let Some(parent) = salt_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut salt = vec![0; SALT_LEN];
+    fill_random(&mut salt)?;
+    match OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(salt_path)
+    {
+        Ok(mut file) => {
+            file.write_all(&salt)?;
+            return Ok(salt);
+        }
+        Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {}
+        Err(err) => return Err(err),
+    }
+
+    // The winner may not have written its salt yet
+    for _ in 0..50 {
+        let salt = fs::read(salt_path)?;
+        if salt.len() >= SALT_LEN {
+            return Ok(salt);
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+    Err(io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("{} is too short to be a salt", salt_path.display()),
+    ))
+}
+
+fn fill_random(bytes: &mut [u8]) -> io::Result<()> {
+    SystemRandom::new()
+        .fill(bytes)
+        .map_err(|_| io::Error::other("no system randomness available"))
+}
+
+fn crypto_error() -> io::Error {
+    io::Error::other("encryption failed")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(passphrase: &str, salt_path: &Path) -> CacheKey {
+        CacheKey::derive(&KeySource::Passphrase(passphrase.to_string()), salt_path).unwrap()
+    }
+
+    #[test]
+    fn round_trips_and_binds_the_context() {
+        let dir = tempfile::tempdir().unwrap();
+        let salt = dir.path().join("salt");
+        let sealed = key("hunter2", &salt)
+            .seal("2024 day 1", b"3   4\n")
+            .unwrap();
+        assert!(!sealed.windows(5).any(|w| w == b"3   4"));
+
+        let key = key("hunter2", &salt);
+        assert_eq!(key.open("2024 day 1", &sealed).unwrap(), b"3   4\n");
+        assert!(key.open("2024 day 2", &sealed).is_err());
+    }
+
+    #[test]
+    fn wrong_passphrases_and_garbage_are_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let salt = dir.path().join("salt");
+        let sealed = key("hunter2", &salt).seal("ctx", b"secret").unwrap();
+
+        let err = key("hunter3", &salt).open("ctx", &sealed).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(key("hunter2", &salt).open("ctx", b"3   4\n").is_err());
+    }
+
+    #[test]
+    fn a_salt_created_meanwhile_is_used_instead_of_replaced() {
+        let dir = tempfile::tempdir().unwrap();
+        let salt_path = dir.path().join("salt");
+        let salt = create_salt(&salt_path).unwrap();
+        assert_eq!(salt.len(), SALT_LEN);
+
+        // A process that lost the race reads the winner's salt
+        assert_eq!(create_salt(&salt_path).unwrap(), salt);
+        assert_eq!(fs::read(&salt_path).unwrap(), salt);
+    }
+
+    #[test]
+    fn key_files_are_read_and_never_printed() {
+        let dir = tempfile::tempdir().unwrap();
+        let key_file = dir.path().join("key");
+        fs::write(&key_file, "file secret").unwrap();
+        let source = KeySource::KeyFile(key_file);
+        assert!(CacheKey::derive(&source, &dir.path().join("salt")).is_ok());
+
+        let passphrase = KeySource::Passphrase("hunter2".to_string());
+        assert!(!format!("{:?}", passphrase).contains("hunter2"));
+    }
+}
@@ -0,0 +1,218 @@
+use crate::html::{self, Element, Node};
+
+/// Converts the `<article class="day-desc">` blocks of a puzzle page to Markdown.
+/// Part two only appears once part one is solved. Returns `None` when the page
+/// has no puzzle text at all.
+pub fn description_to_markdown(page: &str, base_url: &str) -> Option<String> {
+    let nodes = html::parse(page);
+    let articles = html::find_all(&nodes, &|e| e.name == "article" && e.has_class("day-desc"));
+    if articles.is_empty() {
+        return None;
+    }
+
+    let mut markdown = String::new();
+    for article in articles {
+        render_blocks(&article.children, base_url, &mut markdown);
+    }
+    Some(markdown.trim_end().to_string() + "\n")
+}
+
+/// Whether a description from `description_to_markdown` includes part two.
+pub(crate) fn has_part_two(markdown: &str) -> bool {
+    markdown.lines().any(|line| line == "## --- Part Two ---")
+}
+
+fn render_blocks(nodes: &[Node], base_url: &str, out: &mut String) {
+    for node in nodes {
+        let element = match node {
+            Node::Element(element) => element,
+            Node::Text(text) => {
+                let text = collapse_whitespace(&escape(text));
+                if !text.trim().is_empty() {
+                    out.push_str(text.trim());
+                    out.push_str("\n\n");
+                }
+                continue;
+            }
+        };
+
+        match element.name.as_str() {
+            "h2" => {
+                out.push_str("## ");
+                out.push_str(render_inline(&element.children, base_url).trim());
+                out.push_str("\n\n");
+            }
+            "p" => {
+                out.push_str(render_inline(&element.children, base_url).trim());
+                out.push_str("\n\n");
+            }
+            "pre" => {
+                // Emphasis inside example blocks cannot be shown in a fence, so keep the text
+                out.push_str("```\n");
+                out.push_str(element.text().trim_end_matches('\n'));
+                out.push_str("\n```\n\n");
+            }
+            "ul" | "ol" => {
+                for (index, item) in element.elements().filter(|e| e.name == "li").enumerate() {
+                    if element.name == "ol" {
+                        out.push_str(&format!("{}. ", index + 1));
+                    } else {
+                        out.push_str("- ");
+                    }
+                    out.push_str(render_inline(&item.children, base_url).trim());
+                    out.push('\n');
+                }
+                out.push('\n');
+            }
+            _ => render_blocks(&element.children, base_url, out),
+        }
+    }
+}
+
+fn render_inline(nodes: &[Node], base_url: &str) -> String {
+    let mut out = String::new();
+    for node in nodes {
+        match node {
+            Node::Text(text) => out.push_str(&collapse_whitespace(&escape(text))),
+            Node::Element(element) => out.push_str(&render_element(element, base_url)),
+        }
+    }
+    out
+}
+
+fn render_element(element: &Element, base_url: &str) -> String {
+    match element.name.as_str() {
+        "em" => format!("*{}*", render_inline(&element.children, base_url).trim()),
+        "code" => {
+            let code = inline_code(&element.text());
+            // `<code><em>` highlights a value; carry the emphasis outside the code span
+            if element.contains(&|e| e.name == "em") {
+                format!("*{}*", code)
+            } else {
+                code
+            }
+        }
+        "a" => {
+            let text = render_inline(&element.children, base_url);
+            match element.attr("href") {
+                Some(href) => format!("[{}]({})", text.trim(), absolute_url(href, base_url)),
+                None => text,
+            }
+        }
+        "br" => "  \n".to_string(),
+        _ => render_inline(&element.children, base_url),
+    }
+}
+
+fn inline_code(code: &str) -> String {
+    if code.contains('`') {
+        format!("`` {} ``", code)
+    } else {
+        format!("`{}`", code)
+    }
+}
+
+fn absolute_url(href: &str, base_url: &str) -> String {
+    if href.starts_with('/') {
+        format!("{}{}", base_url, href)
+    } else {
+        href.to_string()
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '*' | '_' | '`' | '[' | ']') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+// Source newlines and indentation inside prose are not meaningful
+fn collapse_whitespace(text: &str) -> String {
+    let mut collapsed = String::with_capacity(text.len());
+    let mut in_space = false;
+    for c in text.chars() {
+        if c.is_whitespace() && c != '\u{a0}' {
+            if !in_space {
+                collapsed.push(' ');
+            }
+            in_space = true;
+        } else {
+            collapsed.push(c);
+            in_space = false;
+        }
+    }
+    collapsed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<!DOCTYPE html>
+<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Historian Hysteria ---</h2><p>The <em>Chief Historian</em> is missing.
+See <a href="/2024/about">about</a> or <a href="https://example.com" target="_blank">this</a>.</p>
+<p>For example:</p>
+<pre><code>3   4
+4   3
+<em>2</em>   5
+</code></pre>
+<ul><li>The smallest pair is <code>1</code> and <code>3</code>.</li><li>Then <code>2*3</code>.</li></ul>
+<p>The total distance is <code><em>11</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>1234</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Compute a <span title="Tooltip">similarity</span> score: <em><code>31</code></em>.</p></article>
+</main></body></html>"#;
+
+    #[test]
+    fn converts_both_parts() {
+        let markdown = description_to_markdown(PAGE, "https://adventofcode.com").unwrap();
+        assert_eq!(
+            markdown,
+            "## --- Day 1: Historian Hysteria ---
+
+The *Chief Historian* is missing. See [about](https://adventofcode.com/2024/about) or [this](https://example.com).
+
+For example:
+
+```
+3   4
+4   3
+2   5
+```
+
+- The smallest pair is `1` and `3`.
+- Then `2*3`.
+
+The total distance is *`11`*.
+
+## --- Part Two ---
+
+Compute a similarity score: *`31`*.
+"
+        );
+        assert!(has_part_two(&markdown));
+    }
+
+    #[test]
+    fn escapes_markdown_in_prose() {
+        let page = r#"<article class="day-desc"><p>2*3 is_not [x]</p></article>"#;
+        assert_eq!(
+            description_to_markdown(page, "").unwrap(),
+            "2\\*3 is\\_not \\[x\\]\n"
+        );
+    }
+
+    #[test]
+    fn pages_without_articles_have_no_description() {
+        assert_eq!(
+            description_to_markdown("<html><body>Log in</body></html>", ""),
+            None
+        );
+    }
+}
@@ -0,0 +1,225 @@
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use reqwest::StatusCode;
+use tokio::task::JoinError;
+
+use crate::client::OFFLINE_ENV_VAR;
+use crate::profile::Profile;
+use crate::puzzle::{format_hms, PuzzleId, PuzzleIdError};
+use crate::session::{SessionSource, SESSION_ENV_VAR};
+use crate::validate::InvalidInput;
+
+/// Everything that can go wrong while getting a puzzle input.
+#[derive(Debug)]
+pub enum FetchError {
+    /// Reading or writing a local file failed.
+    Io { path: PathBuf, source: io::Error },
+    /// The site answered with an unexpected status code.
+    Http { url: String, status: StatusCode },
+    /// No session token was found anywhere.
+    MissingSession { session_file: Option<PathBuf> },
+    /// A profile has no session file, or an empty one.
+    MissingProfile {
+        profile: Profile,
+        session_file: Option<PathBuf>,
+    },
+    /// A profile name that is not letters, digits, `-` and `_`.
+    InvalidProfile(String),
+    /// The site rejected the session token.
+    ExpiredSession { source: SessionSource },
+    /// The puzzle has not been released yet. `remaining` is known when the request
+    /// was refused locally; a 404 from the site leaves it `None`.
+    PuzzleNotYetUnlocked {
+        id: PuzzleId,
+        remaining: Option<Duration>,
+    },
+    /// Offline mode is on and the page is not in the cache.
+    NotCached { url: String },
+    /// The request never got a response.
+    Network {
+        url: String,
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    /// The response was successful but is not a puzzle input.
+    InvalidContent { url: String, reason: InvalidInput },
+    /// A page did not contain what it was fetched for.
+    UnexpectedPage { url: String, expected: &'static str },
+    /// The requested day or year does not exist.
+    InvalidPuzzle(PuzzleIdError),
+    /// A concurrent fetch panicked or was cancelled before it finished.
+    TaskFailed(JoinError),
+}
+
+impl FetchError {
+    pub(crate) fn io(path: impl Into<PathBuf>, source: io::Error) -> Self {
+        FetchError::Io {
+            path: path.into(),
+            source,
+        }
+    }
+
+    pub(crate) fn network(
+        url: &str,
+        source: impl Into<Box<dyn std::error::Error + Send + Sync>>,
+    ) -> Self {
+        FetchError::Network {
+            url: url.to_string(),
+            source: source.into(),
+        }
+    }
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::Io { path, source } => write!(f, "{}: {}", path.display(), source),
+            FetchError::Http { url, status } => write!(f, "{} returned {}", url, status),
+            FetchError::MissingSession { session_file } => {
+                write!(f, "no session token: set {}", SESSION_ENV_VAR)?;
+                if let Some(path) = session_file {
+                    write!(f, ", write it to {}", path.display())?;
+                }
+                write!(f, " or enter it when prompted")
+            }
+            FetchError::MissingProfile {
+                profile,
+                session_file,
+            } => {
+                write!(f, "no session token for profile {}", profile)?;
+                match session_file {
+                    Some(path) => write!(f, ": write it to {}", path.display()),
+                    None => write!(f, ": no config directory (set HOME or XDG_CONFIG_HOME)"),
+                }
+            }
+            FetchError::InvalidProfile(name) => write!(
+                f,
+                "invalid profile name {:?}: use letters, digits, '-' and '_'",
+                name
+            ),
+            FetchError::ExpiredSession { source } => write!(
+                f,
+                "the session token from {} has expired or was rejected. Log in at \
+                 adventofcode.com, copy the `session` cookie from your browser's developer \
+                 tools (Storage or Application, then Cookies) and {}",
+                source,
+                refresh_instructions(source)
+            ),
+            FetchError::PuzzleNotYetUnlocked {
+                id,
+                remaining: Some(remaining),
+            } => write!(f, "{} unlocks in {}", id, format_hms(*remaining)),
+            FetchError::PuzzleNotYetUnlocked {
+                id,
+                remaining: None,
+            } => {
+                write!(f, "{} is not unlocked yet", id)
+            }
+            FetchError::NotCached { url } => write!(
+                f,
+                "{} is not cached and offline mode is on (unset {} to download it)",
+                url, OFFLINE_ENV_VAR
+            ),
+            FetchError::Network { url, source } => {
+                write!(f, "request to {} failed: {}", url, source)
+            }
+            FetchError::InvalidContent { url, reason } => {
+                write!(f, "{} did not return a puzzle input: {}", url, reason)
+            }
+            FetchError::UnexpectedPage { url, expected } => {
+                write!(f, "{} did not contain {}", url, expected)
+            }
+            FetchError::InvalidPuzzle(err) => write!(f, "{}", err),
+            FetchError::TaskFailed(err) => write!(f, "fetch did not finish: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for FetchError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            FetchError::Io { source, .. } => Some(source),
+            FetchError::Network { source, .. } => Some(source.as_ref()),
+            FetchError::InvalidPuzzle(err) => Some(err),
+            FetchError::TaskFailed(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+fn refresh_instructions(source: &SessionSource) -> String {
+    match source {
+        SessionSource::Argument => "pass the new token instead".to_string(),
+        SessionSource::Environment => format!("export it as {}", SESSION_ENV_VAR),
+        SessionSource::ConfigFile(path) => format!("replace the contents of {}", path.display()),
+        SessionSource::Prompt => "paste it at the prompt".to_string(),
+    }
+}
+
+impl From<PuzzleIdError> for FetchError {
+    fn from(err: PuzzleIdError) -> Self {
+        FetchError::InvalidPuzzle(err)
+    }
+}
+
+// Maps a failed response to the most specific error; a 404 only means "locked"
+// for puzzle pages
+pub(crate) fn status_error(
+    url: &str,
+    status: StatusCode,
+    id: Option<PuzzleId>,
+    source: &SessionSource,
+) -> FetchError {
+    match (status, id) {
+        // AoC answers 400 "Please log in" when the cookie is missing or stale
+        (StatusCode::BAD_REQUEST, _) => FetchError::ExpiredSession {
+            source: source.clone(),
+        },
+        (StatusCode::NOT_FOUND, Some(id)) => FetchError::PuzzleNotYetUnlocked {
+            id,
+            remaining: None,
+        },
+        _ => FetchError::Http {
+            url: url.to_string(),
+            status,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_statuses_to_specific_errors() {
+        let id = PuzzleId::new(2024, 25).unwrap();
+        let source = SessionSource::Environment;
+
+        assert!(matches!(
+            status_error("url", StatusCode::BAD_REQUEST, Some(id), &source),
+            FetchError::ExpiredSession {
+                source: SessionSource::Environment
+            }
+        ));
+        assert!(matches!(
+            status_error("url", StatusCode::NOT_FOUND, Some(id), &source),
+            FetchError::PuzzleNotYetUnlocked { id: unlocked, remaining: None } if unlocked == id
+        ));
+        assert!(matches!(
+            status_error("url", StatusCode::BAD_GATEWAY, Some(id), &source),
+            FetchError::Http {
+                status: StatusCode::BAD_GATEWAY,
+                ..
+            }
+        ));
+        assert!(matches!(
+            status_error("url", StatusCode::NOT_FOUND, None, &source),
+            FetchError::Http {
+                status: StatusCode::NOT_FOUND,
+                ..
+            }
+        ));
+    }
+}
@@ -0,0 +1,292 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::answer::Answer;
+use crate::html::{self, Node};
+use crate::puzzle::Part;
+
+/// An example input from the puzzle text with the answers the text gives for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub input: String,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl Example {
+    pub fn expected(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.part1.as_deref(),
+            Part::Two => self.part2.as_deref(),
+        }
+    }
+}
+
+/// Pulls the example input and the emphasized answer out of each part of a puzzle page.
+///
+/// The first `<pre><code>` block of a part is taken as its example and the last
+/// `<code><em>` outside it as the answer. Part two reuses the part one example unless
+/// it brings a block of its own. The heuristic fits most days; fixtures are plain files
+/// so the odd one out can be corrected by hand.
+pub fn extract_examples(page: &str) -> Vec<Example> {
+    let nodes = html::parse(page);
+    let articles = html::find_all(&nodes, &|e| e.name == "article" && e.has_class("day-desc"));
+
+    let mut examples: Vec<Example> = Vec::new();
+    for (index, article) in articles.iter().take(2).enumerate() {
+        let input = article
+            .find_all(&|e| e.name == "pre")
+            .first()
+            .map(|pre| pre.text());
+        let mut answers = Vec::new();
+        emphasized_code(&article.children, &mut answers);
+        let answer = answers.pop();
+
+        let example = match input {
+            Some(input) if !examples.iter().any(|e| e.input == input) => {
+                examples.push(Example {
+                    input,
+                    part1: None,
+                    part2: None,
+                });
+                examples.last_mut()
+            }
+            Some(input) => examples.iter_mut().find(|e| e.input == input),
+            None => examples.first_mut(),
+        };
+        if let Some(example) = example {
+            if index == 0 {
+                example.part1 = answer;
+            } else {
+                example.part2 = answer;
+            }
+        }
+    }
+    examples
+}
+
+// Collects the text of `<code><em>` and `<em><code>` highlights outside example blocks
+fn emphasized_code(nodes: &[Node], answers: &mut Vec<String>) {
+    for node in nodes {
+        let Node::Element(element) = node else {
+            continue;
+        };
+        let highlighted = (element.name == "code" && element.contains(&|e| e.name == "em"))
+            || (element.name == "em" && element.contains(&|e| e.name == "code"));
+        if highlighted {
+            answers.push(element.text().trim().to_string());
+        } else if element.name != "pre" {
+            emphasized_code(&element.children, answers);
+        }
+    }
+}
+
+/// Writes examples as `examples/N.txt` and `examples/N.expected.partP` under `dir`,
+/// numbered from 1. Existing files are kept so hand corrections survive a re-run;
+/// returns the files that were written.
+pub fn write_examples(dir: &Path, examples: &[Example]) -> io::Result<Vec<PathBuf>> {
+    let examples_dir = dir.join("examples");
+    fs::create_dir_all(&examples_dir)?;
+
+    let mut written = Vec::new();
+    for (index, example) in examples.iter().enumerate() {
+        let number = index + 1;
+        let mut files = vec![(format!("{}.txt", number), example.input.clone())];
+        for part in [Part::One, Part::Two] {
+            if let Some(expected) = example.expected(part) {
+                files.push((expected_file_name(number, part), format!("{}\n", expected)));
+            }
+        }
+
+        for (name, contents) in files {
+            let path = examples_dir.join(name);
+            if !path.exists() {
+                fs::write(&path, contents)?;
+                written.push(path);
+            }
+        }
+    }
+    Ok(written)
+}
+
+/// Runs `solve` on every example fixture under `dir/examples` that has an expected
+/// answer for `part`, panicking with every mismatch. A day without an `examples`
+/// directory is skipped with a message. Used by `example_tests!`.
+pub fn check_examples(dir: impl AsRef<Path>, part: Part, solve: impl Fn(&str) -> Answer) {
+    let examples_dir = dir.as_ref().join("examples");
+    if !examples_dir.is_dir() {
+        eprintln!(
+            "skipping {}: no {}; run `aoc-input examples` to extract them",
+            part,
+            examples_dir.display()
+        );
+        return;
+    }
+    let mut numbers: Vec<usize> = fs::read_dir(&examples_dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name();
+            name.to_str()?.strip_suffix(".txt")?.parse().ok()
+        })
+        .collect();
+    numbers.sort_unstable();
+
+    let mut checked = 0;
+    let mut failures = Vec::new();
+    for number in numbers {
+        let expected_path = examples_dir.join(expected_file_name(number, part));
+        let Ok(expected) = fs::read_to_string(&expected_path) else {
+            continue;
+        };
+        let input_path = examples_dir.join(format!("{}.txt", number));
+        let input = fs::read_to_string(&input_path)
+            .unwrap_or_else(|err| panic!("reading {}: {}", input_path.display(), err));
+
+        checked += 1;
+        let actual = solve(&input);
+        if !actual.matches(&expected) {
+            failures.push(format!(
+                "example {}: expected {}, got {}",
+                number,
+                expected.trim(),
+                actual
+            ));
+        }
+    }
+
+    assert!(
+        checked > 0,
+        "no examples with an expected {} answer in {}; run `aoc-input examples` to extract them",
+        part,
+        examples_dir.display()
+    );
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+fn expected_file_name(number: usize, part: Part) -> String {
+    format!("{}.expected.part{}", number, part.level())
+}
+
+/// Generates `part1` and `part2` tests that check a day's solutions against its
+/// `examples/` fixtures. Invoke at the crate root of a day with its `Solution`, or
+/// with two `&str -> impl ToString` functions:
+///
+/// ```ignore
+/// get_input::example_tests!(Day01);
+/// get_input::example_tests!(part1, part2);
+/// ```
+#[macro_export]
+macro_rules! example_tests {
+    ($solution:ty) => {
+        $crate::example_tests!(
+            |input| <$solution as $crate::Solution>::solve(input, $crate::Part::One).unwrap(),
+            |input| <$solution as $crate::Solution>::solve(input, $crate::Part::Two).unwrap()
+        );
+    };
+    ($part1:expr, $part2:expr) => {
+        #[cfg(test)]
+        mod example_tests {
+            #[allow(unused_imports)]
+            use super::*;
+
+            #[test]
+            fn part1_matches_examples() {
+                $crate::check_examples(env!("CARGO_MANIFEST_DIR"), $crate::Part::One, |input| {
+                    $crate::Answer::parse(&($part1)(input).to_string())
+                });
+            }
+
+            #[test]
+            fn part2_matches_examples() {
+                $crate::check_examples(env!("CARGO_MANIFEST_DIR"), $crate::Part::Two, |input| {
+                    $crate::Answer::parse(&($part2)(input).to_string())
+                });
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 1: Historian Hysteria ---</h2>
+<p>For example:</p>
+<pre><code>3   4
+4   3
+<em>2</em>   5
+</code></pre>
+<p>The smallest number is <code><em>1</em></code>, but the total distance is <code><em>11</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>1234</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>The similarity score is <em><code>31</code></em>.</p></article>
+</main>"#;
+
+    #[test]
+    fn part_two_reuses_the_part_one_example() {
+        assert_eq!(
+            extract_examples(PAGE),
+            vec![Example {
+                input: "3   4\n4   3\n2   5\n".to_string(),
+                part1: Some("11".to_string()),
+                part2: Some("31".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn part_two_can_bring_its_own_example() {
+        let page = r#"<article class="day-desc"><pre><code>mul(2,4)</code></pre><p><code><em>8</em></code></p></article>
+<article class="day-desc"><pre><code>don't()mul(2,4)</code></pre><p><code><em>0</em></code></p></article>"#;
+        let examples = extract_examples(page);
+        assert_eq!(examples.len(), 2);
+        assert_eq!(examples[0].part2, None);
+        assert_eq!(examples[1].input, "don't()mul(2,4)");
+        assert_eq!(examples[1].part1, None);
+        assert_eq!(examples[1].part2.as_deref(), Some("0"));
+    }
+
+    #[test]
+    fn fixtures_round_trip_and_survive_rewrites() {
+        let dir = tempfile::tempdir().unwrap();
+        let examples = extract_examples(PAGE);
+        let written = write_examples(dir.path(), &examples).unwrap();
+        assert_eq!(written.len(), 3);
+        assert_eq!(
+            fs::read_to_string(dir.path().join("examples/1.expected.part1")).unwrap(),
+            "11\n"
+        );
+
+        fs::write(dir.path().join("examples/1.expected.part2"), "32\n").unwrap();
+        assert!(write_examples(dir.path(), &examples).unwrap().is_empty());
+
+        check_examples(dir.path(), Part::One, |_| Answer::from(11));
+    }
+
+    #[test]
+    #[should_panic(expected = "example 1: expected 11, got 0")]
+    fn mismatches_fail_the_check() {
+        let dir = tempfile::tempdir().unwrap();
+        write_examples(dir.path(), &extract_examples(PAGE)).unwrap();
+        check_examples(dir.path(), Part::One, |_| Answer::from(0));
+    }
+
+    #[test]
+    fn days_without_examples_are_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        check_examples(dir.path(), Part::One, |_| Answer::from(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "no examples")]
+    fn fixtures_without_the_part_fail_the_check() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("examples")).unwrap();
+        fs::write(dir.path().join("examples/1.txt"), "1 2\n").unwrap();
+        check_examples(dir.path(), Part::Two, |_| Answer::from(0));
+    }
+}
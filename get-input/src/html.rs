@@ -0,0 +1,315 @@
+//! Just enough HTML parsing for the well-formed pages Advent of Code serves.
+
+const VOID_ELEMENTS: [&str; 8] = ["br", "hr", "img", "input", "meta", "link", "area", "wbr"];
+const RAW_TEXT_ELEMENTS: [&str; 2] = ["script", "style"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Node {
+    Element(Element),
+    Text(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub(crate) struct Element {
+    pub name: String,
+    pub attrs: Vec<(String, String)>,
+    pub children: Vec<Node>,
+}
+
+impl Element {
+    pub fn attr(&self, name: &str) -> Option<&str> {
+        self.attrs
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn has_class(&self, class: &str) -> bool {
+        self.attr("class")
+            .is_some_and(|classes| classes.split_whitespace().any(|c| c == class))
+    }
+
+    /// All text below this element, with tags removed.
+    pub fn text(&self) -> String {
+        let mut text = String::new();
+        collect_text(&self.children, &mut text);
+        text
+    }
+
+    pub fn elements(&self) -> impl Iterator<Item = &Element> {
+        self.children.iter().filter_map(|node| match node {
+            Node::Element(element) => Some(element),
+            Node::Text(_) => None,
+        })
+    }
+
+    /// Descendants matching `predicate`, in document order.
+    pub fn find_all<'a>(&'a self, predicate: &dyn Fn(&Element) -> bool) -> Vec<&'a Element> {
+        find_all(&self.children, predicate)
+    }
+
+    pub fn contains(&self, predicate: &dyn Fn(&Element) -> bool) -> bool {
+        !self.find_all(predicate).is_empty()
+    }
+}
+
+/// Elements anywhere in `nodes` matching `predicate`, in document order.
+pub(crate) fn find_all<'a>(
+    nodes: &'a [Node],
+    predicate: &dyn Fn(&Element) -> bool,
+) -> Vec<&'a Element> {
+    let mut found = Vec::new();
+    for node in nodes {
+        if let Node::Element(element) = node {
+            if predicate(element) {
+                found.push(element);
+            }
+            found.extend(find_all(&element.children, predicate));
+        }
+    }
+    found
+}
+
+fn collect_text(nodes: &[Node], text: &mut String) {
+    for node in nodes {
+        match node {
+            Node::Text(t) => text.push_str(t),
+            Node::Element(element) => collect_text(&element.children, text),
+        }
+    }
+}
+
+/// Parses a document into a forest of nodes. Unknown closing tags are ignored and
+/// unclosed elements are closed at the end of the input.
+pub(crate) fn parse(html: &str) -> Vec<Node> {
+    let mut stack = vec![Element::default()];
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(start) = rest.find('<') else {
+            push_text(&mut stack, rest);
+            break;
+        };
+        push_text(&mut stack, &rest[..start]);
+        rest = &rest[start..];
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+        } else if rest.starts_with("<!") || rest.starts_with("<?") {
+            rest = rest.find('>').map_or("", |end| &rest[end + 1..]);
+        } else if let Some(closing) = rest.strip_prefix("</") {
+            let end = closing.find('>').unwrap_or(closing.len());
+            close(&mut stack, &closing[..end].trim().to_ascii_lowercase());
+            rest = closing.get(end + 1..).unwrap_or("");
+        } else if let Some((element, self_closing, after)) = parse_tag(&rest[1..]) {
+            rest = after;
+            if RAW_TEXT_ELEMENTS.contains(&element.name.as_str()) {
+                // Script and style contents are not text; skip them entirely
+                let end_tag = format!("</{}", element.name);
+                let end = rest.to_ascii_lowercase().find(&end_tag);
+                rest = end.map_or("", |end| &rest[end..]);
+                rest = rest.find('>').map_or("", |end| &rest[end + 1..]);
+                continue;
+            }
+            if self_closing || VOID_ELEMENTS.contains(&element.name.as_str()) {
+                push_node(&mut stack, Node::Element(element));
+            } else {
+                stack.push(element);
+            }
+        } else {
+            // A lone '<' in text
+            push_text(&mut stack, "<");
+            rest = &rest[1..];
+        }
+    }
+
+    while stack.len() > 1 {
+        let element = stack.pop().unwrap();
+        push_node(&mut stack, Node::Element(element));
+    }
+    stack.pop().unwrap().children
+}
+
+fn push_node(stack: &mut [Element], node: Node) {
+    stack.last_mut().unwrap().children.push(node);
+}
+
+fn push_text(stack: &mut [Element], raw: &str) {
+    if !raw.is_empty() {
+        push_node(stack, Node::Text(decode_entities(raw)));
+    }
+}
+
+fn close(stack: &mut Vec<Element>, name: &str) {
+    let Some(position) = stack.iter().rposition(|element| element.name == name) else {
+        return;
+    };
+    if position == 0 {
+        return;
+    }
+    while stack.len() > position {
+        let element = stack.pop().unwrap();
+        push_node(stack, Node::Element(element));
+    }
+}
+
+// Parses `name attr="value" ...>` and returns the element, whether it ended in `/>`,
+// and the input after the tag
+fn parse_tag(tag: &str) -> Option<(Element, bool, &str)> {
+    let name_end = tag
+        .find(|c: char| c.is_whitespace() || c == '>' || c == '/')
+        .unwrap_or(tag.len());
+    let name = &tag[..name_end];
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+        return None;
+    }
+
+    let mut element = Element {
+        name: name.to_ascii_lowercase(),
+        ..Element::default()
+    };
+    let mut rest = &tag[name_end..];
+    loop {
+        rest = rest.trim_start();
+        if let Some(after) = rest.strip_prefix("/>") {
+            return Some((element, true, after));
+        }
+        if let Some(after) = rest.strip_prefix('>') {
+            return Some((element, false, after));
+        }
+        if let Some(after) = rest.strip_prefix('/') {
+            rest = after;
+            continue;
+        }
+        if rest.is_empty() {
+            return Some((element, false, rest));
+        }
+
+        let key_end = rest
+            .find(|c: char| c.is_whitespace() || c == '=' || c == '>' || c == '/')
+            .unwrap_or(rest.len());
+        let key = rest[..key_end].to_ascii_lowercase();
+        rest = rest[key_end..].trim_start();
+
+        let mut value = String::new();
+        if let Some(after) = rest.strip_prefix('=') {
+            let after = after.trim_start();
+            if let Some(quote) = after.chars().next().filter(|c| *c == '"' || *c == '\'') {
+                let body = &after[1..];
+                let end = body.find(quote).unwrap_or(body.len());
+                value = decode_entities(&body[..end]);
+                rest = body.get(end + 1..).unwrap_or("");
+            } else {
+                let end = after
+                    .find(|c: char| c.is_whitespace() || c == '>')
+                    .unwrap_or(after.len());
+                value = decode_entities(&after[..end]);
+                rest = &after[end..];
+            }
+        }
+        if key.is_empty() {
+            // Skip a character we cannot make sense of
+            rest = rest.get(1..).unwrap_or("");
+        } else {
+            element.attrs.push((key, value));
+        }
+    }
+}
+
+/// Decodes the named entities AoC uses and all numeric character references.
+pub(crate) fn decode_entities(raw: &str) -> String {
+    let mut decoded = String::with_capacity(raw.len());
+    let mut rest = raw;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let entity = rest
+            .find(';')
+            .filter(|&end| end <= 10)
+            .and_then(|end| decode_entity(&rest[1..end]).map(|c| (c, end)));
+        match entity {
+            Some((c, end)) => {
+                decoded.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+fn decode_entity(entity: &str) -> Option<char> {
+    match entity {
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "amp" => Some('&'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some('\u{a0}'),
+        _ => {
+            let number = entity.strip_prefix('#')?;
+            let code = match number.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => number.parse().ok()?,
+            };
+            char::from_u32(code)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn element(nodes: &[Node]) -> &Element {
+        match &nodes[0] {
+            Node::Element(element) => element,
+            Node::Text(text) => panic!("expected an element, got {:?}", text),
+        }
+    }
+
+    #[test]
+    fn builds_a_tree_with_attributes() {
+        let nodes = parse(
+            r#"<article class="day-desc"><h2>--- Day 1 ---</h2><p>Hi <em>there</em></p></article>"#,
+        );
+        let article = element(&nodes);
+        assert_eq!(article.name, "article");
+        assert!(article.has_class("day-desc"));
+        assert_eq!(article.text(), "--- Day 1 ---Hi there");
+        assert_eq!(article.find_all(&|e| e.name == "em").len(), 1);
+    }
+
+    #[test]
+    fn decodes_entities() {
+        let nodes = parse("<code>a &lt; b &amp;&amp; c &gt; d &#39;x&#x27;</code>");
+        assert_eq!(element(&nodes).text(), "a < b && c > d 'x'");
+        assert_eq!(decode_entities("AT&T; &bogus;"), "AT&T; &bogus;");
+    }
+
+    #[test]
+    fn skips_comments_doctype_and_scripts() {
+        let nodes = parse("<!DOCTYPE html><!-- hi --><script>if (a < b) {}</script><p>text</p>");
+        assert_eq!(nodes.len(), 1);
+        assert_eq!(element(&nodes).text(), "text");
+    }
+
+    #[test]
+    fn handles_void_and_unclosed_elements() {
+        let nodes = parse("<p>one<br>two<img src=x.png/><span>three</p>");
+        let p = element(&nodes);
+        assert_eq!(p.text(), "onetwothree");
+        assert_eq!(p.elements().count(), 3);
+    }
+
+    #[test]
+    fn keeps_lone_angle_brackets_as_text() {
+        let nodes = parse("<pre><code>a < b\n1 <= 2</code></pre>");
+        assert_eq!(element(&nodes).text(), "a < b\n1 <= 2");
+    }
+}
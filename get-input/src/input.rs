@@ -0,0 +1,213 @@
+use std::error::Error;
+use std::fmt;
+use std::ops::Deref;
+
+/// The layout a day's input is expected to have, checked by `PuzzleInput::expect`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shape {
+    /// Every line has the same number of characters, e.g. a map.
+    Rectangular,
+    /// Exactly one line, e.g. a disk map or a single instruction string.
+    SingleLine,
+}
+
+/// Why an input does not have the expected `Shape`. Lines are numbered from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShapeError {
+    Empty,
+    Ragged {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    MultipleLines(usize),
+}
+
+impl fmt::Display for ShapeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShapeError::Empty => write!(f, "the input is empty"),
+            ShapeError::Ragged {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {} has {} characters where line 1 has {}",
+                line, found, expected
+            ),
+            ShapeError::MultipleLines(count) => {
+                write!(f, "expected a single line, found {}", count)
+            }
+        }
+    }
+}
+
+impl Error for ShapeError {}
+
+/// A puzzle input with LF line endings and the final newline removed, so solutions
+/// do not trip over CRLF files or a blank last line. Derefs to `str`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PuzzleInput {
+    text: String,
+}
+
+impl PuzzleInput {
+    pub fn new(raw: &str) -> Self {
+        let mut text = raw.replace("\r\n", "\n").replace('\r', "\n");
+        if text.ends_with('\n') {
+            text.pop();
+        }
+        PuzzleInput { text }
+    }
+
+    /// Checks the input has the given shape, for days whose parsing relies on it.
+    pub fn expect(self, shape: Shape) -> Result<Self, ShapeError> {
+        if self.text.is_empty() {
+            return Err(ShapeError::Empty);
+        }
+        match shape {
+            Shape::Rectangular => {
+                let mut lines = self.lines();
+                let expected = lines.next().map_or(0, |line| line.chars().count());
+                for (index, line) in lines.enumerate() {
+                    let found = line.chars().count();
+                    if found != expected {
+                        return Err(ShapeError::Ragged {
+                            line: index + 2,
+                            expected,
+                            found,
+                        });
+                    }
+                }
+            }
+            Shape::SingleLine => {
+                let count = self.lines().count();
+                if count != 1 {
+                    return Err(ShapeError::MultipleLines(count));
+                }
+            }
+        }
+        Ok(self)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    pub fn lines(&self) -> std::str::Lines<'_> {
+        self.text.lines()
+    }
+
+    /// Groups of lines separated by blank lines, e.g. rules then updates.
+    pub fn blocks(&self) -> impl Iterator<Item = &str> {
+        self.text
+            .split("\n\n")
+            .map(|block| block.trim_matches('\n'))
+            .filter(|block| !block.is_empty())
+    }
+
+    /// One row of characters per line. Rows may differ in length unless the input
+    /// was checked with `Shape::Rectangular`.
+    pub fn grid(&self) -> Vec<Vec<char>> {
+        self.lines().map(|line| line.chars().collect()).collect()
+    }
+
+    pub fn into_string(self) -> String {
+        self.text
+    }
+}
+
+impl Deref for PuzzleInput {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.text
+    }
+}
+
+impl AsRef<str> for PuzzleInput {
+    fn as_ref(&self) -> &str {
+        &self.text
+    }
+}
+
+impl fmt::Display for PuzzleInput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+impl From<&str> for PuzzleInput {
+    fn from(raw: &str) -> Self {
+        PuzzleInput::new(raw)
+    }
+}
+
+impl From<String> for PuzzleInput {
+    fn from(raw: String) -> Self {
+        PuzzleInput::new(&raw)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_line_endings_and_one_trailing_newline() {
+        assert_eq!(PuzzleInput::new("a\r\nb\r\n").as_str(), "a\nb");
+        assert_eq!(PuzzleInput::new("a\rb").as_str(), "a\nb");
+        assert_eq!(PuzzleInput::new("a\n\n").as_str(), "a\n");
+        assert_eq!(PuzzleInput::new("12345").as_str(), "12345");
+    }
+
+    #[test]
+    fn splits_blocks_on_blank_lines() {
+        let input = PuzzleInput::new("47|53\r\n97|13\r\n\r\n75,47,61\r\n97,61\r\n");
+        let blocks: Vec<&str> = input.blocks().collect();
+        assert_eq!(blocks, ["47|53\n97|13", "75,47,61\n97,61"]);
+        assert_eq!(input.lines().count(), 5);
+    }
+
+    #[test]
+    fn grids_and_rectangular_checks() {
+        let input = PuzzleInput::new("MMS\nAXA\nSAM\n")
+            .expect(Shape::Rectangular)
+            .unwrap();
+        assert_eq!(input.grid()[1], ['A', 'X', 'A']);
+
+        assert_eq!(
+            PuzzleInput::new("MMS\nAX\nSAM").expect(Shape::Rectangular),
+            Err(ShapeError::Ragged {
+                line: 2,
+                expected: 3,
+                found: 2
+            })
+        );
+    }
+
+    #[test]
+    fn single_line_checks() {
+        assert!(PuzzleInput::new("2333133121414131402\n")
+            .expect(Shape::SingleLine)
+            .is_ok());
+        assert_eq!(
+            PuzzleInput::new("1\n2\n").expect(Shape::SingleLine),
+            Err(ShapeError::MultipleLines(2))
+        );
+        assert_eq!(
+            PuzzleInput::new("\n").expect(Shape::SingleLine),
+            Err(ShapeError::Empty)
+        );
+    }
+
+    #[test]
+    fn derefs_to_str_for_existing_solutions() {
+        fn part1(input: &str) -> usize {
+            input.len()
+        }
+        let input = PuzzleInput::from("abc\n".to_string());
+        assert_eq!(part1(&input), 3);
+    }
+}
@@ -0,0 +1,331 @@
+use std::collections::{BTreeMap, HashMap};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::Deserialize;
+
+use crate::puzzle::{format_hms, PuzzleId};
+
+/// AoC asks that private leaderboards are polled at most once every 15 minutes.
+pub const LEADERBOARD_MAX_AGE: Duration = Duration::from_secs(15 * 60);
+
+/// Seconds since the Unix epoch, as the leaderboard API reports them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp(pub u64);
+
+impl Timestamp {
+    pub fn system_time(self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(self.0)
+    }
+
+    /// How long after the puzzle unlocked this was, or `None` if it was before.
+    pub fn since_unlock(self, id: PuzzleId) -> Option<Duration> {
+        self.system_time().duration_since(id.unlock_time()).ok()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Star {
+    pub earned: Timestamp,
+    /// Global order in which stars were earned, used to break ties.
+    pub index: u64,
+}
+
+/// A member's stars for one day. Part two can only be earned after part one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DayProgress {
+    pub part1: Star,
+    pub part2: Option<Star>,
+}
+
+impl DayProgress {
+    /// Time from the first star to the second.
+    pub fn delta(&self) -> Option<Duration> {
+        let part2 = self.part2?;
+        Some(Duration::from_secs(
+            part2.earned.0.saturating_sub(self.part1.earned.0),
+        ))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Member {
+    pub id: u64,
+    /// `None` for anonymous users.
+    pub name: Option<String>,
+    pub local_score: u64,
+    pub stars: u32,
+    pub last_star: Option<Timestamp>,
+    pub days: BTreeMap<u8, DayProgress>,
+}
+
+impl Member {
+    /// The name the site shows, including for anonymous users.
+    pub fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+}
+
+/// A private leaderboard, members ordered by local score as on the site.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Leaderboard {
+    pub year: u16,
+    pub owner_id: u64,
+    pub members: Vec<Member>,
+}
+
+#[derive(Deserialize)]
+struct RawLeaderboard {
+    event: String,
+    owner_id: u64,
+    members: HashMap<String, RawMember>,
+}
+
+#[derive(Deserialize)]
+struct RawMember {
+    id: u64,
+    name: Option<String>,
+    local_score: u64,
+    stars: u32,
+    last_star_ts: u64,
+    completion_day_level: HashMap<String, HashMap<String, RawStar>>,
+}
+
+#[derive(Deserialize)]
+struct RawStar {
+    get_star_ts: u64,
+    star_index: u64,
+}
+
+impl Leaderboard {
+    /// Parses the JSON served at `/{year}/leaderboard/private/view/{id}.json`.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        let raw: RawLeaderboard = serde_json::from_str(json)?;
+        let year = raw
+            .event
+            .parse()
+            .map_err(|_| serde::de::Error::custom(format!("invalid event year {:?}", raw.event)))?;
+
+        let mut members: Vec<Member> = raw
+            .members
+            .into_values()
+            .map(|member| Member {
+                id: member.id,
+                name: member.name,
+                local_score: member.local_score,
+                stars: member.stars,
+                last_star: (member.last_star_ts > 0).then_some(Timestamp(member.last_star_ts)),
+                days: member
+                    .completion_day_level
+                    .into_iter()
+                    .filter_map(|(day, parts)| Some((day.parse().ok()?, day_progress(parts)?)))
+                    .collect(),
+            })
+            .collect();
+        // Higher score first; the earlier last star wins a tie, as on the site
+        members.sort_by_key(|member| {
+            (
+                std::cmp::Reverse(member.local_score),
+                member.last_star.unwrap_or(Timestamp(u64::MAX)),
+                member.id,
+            )
+        });
+
+        Ok(Leaderboard {
+            year,
+            owner_id: raw.owner_id,
+            members,
+        })
+    }
+
+    /// One row per member: rank, score, stars and a cell per day, `*` for both
+    /// stars and `.` for part one only.
+    pub fn render_overview(&self) -> String {
+        let last_day = self
+            .members
+            .iter()
+            .filter_map(|member| member.days.keys().next_back())
+            .max()
+            .copied()
+            .unwrap_or(1);
+
+        let mut headers = vec!["#".to_string(), "Score".to_string(), "Stars".to_string()];
+        headers.extend((1..=last_day).map(|day| format!("{:>2}", day)));
+        headers.push("Name".to_string());
+
+        let rows = self
+            .members
+            .iter()
+            .enumerate()
+            .map(|(rank, member)| {
+                let mut row = vec![
+                    format!("{})", rank + 1),
+                    member.local_score.to_string(),
+                    member.stars.to_string(),
+                ];
+                row.extend((1..=last_day).map(|day| {
+                    match member.days.get(&day) {
+                        Some(progress) if progress.part2.is_some() => "*",
+                        Some(_) => ".",
+                        None => "",
+                    }
+                    .to_string()
+                }));
+                row.push(member.display_name());
+                row
+            })
+            .collect();
+        render_table(&headers, rows)
+    }
+
+    /// Completion times for one day, measured from the unlock, with the time taken
+    /// between the two parts. Members are ordered by who finished first.
+    pub fn render_day(&self, day: u8) -> String {
+        let id = PuzzleId::new(self.year, day).ok();
+        let since_unlock = |star: Star| match id.and_then(|id| star.earned.since_unlock(id)) {
+            Some(elapsed) => format_hms(elapsed),
+            None => "-".to_string(),
+        };
+
+        let mut finishers: Vec<(&Member, &DayProgress)> = self
+            .members
+            .iter()
+            .filter_map(|member| Some((member, member.days.get(&day)?)))
+            .collect();
+        finishers.sort_by_key(|(_, progress)| {
+            (
+                progress.part2.map_or(u64::MAX, |star| star.index),
+                progress.part1.index,
+            )
+        });
+
+        let headers = ["#", "Part 1", "Part 2", "Delta", "Name"].map(String::from);
+        let rows = finishers
+            .into_iter()
+            .enumerate()
+            .map(|(rank, (member, progress))| {
+                vec![
+                    format!("{})", rank + 1),
+                    since_unlock(progress.part1),
+                    progress.part2.map_or("-".to_string(), since_unlock),
+                    progress
+                        .delta()
+                        .map_or("-".to_string(), |delta| format!("+{}", format_hms(delta))),
+                    member.display_name(),
+                ]
+            })
+            .collect();
+        render_table(&headers, rows)
+    }
+}
+
+fn day_progress(mut parts: HashMap<String, RawStar>) -> Option<DayProgress> {
+    let star = |raw: RawStar| Star {
+        earned: Timestamp(raw.get_star_ts),
+        index: raw.star_index,
+    };
+    Some(DayProgress {
+        part1: parts.remove("1").map(star)?,
+        part2: parts.remove("2").map(star),
+    })
+}
+
+// Right-aligns every column except the last, which holds names
+fn render_table(headers: &[String], rows: Vec<Vec<String>>) -> String {
+    let mut widths: Vec<usize> = headers.iter().map(|h| h.chars().count()).collect();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut out = String::new();
+    for row in std::iter::once(headers.to_vec()).chain(rows) {
+        let last = row.len() - 1;
+        let cells: Vec<String> = row
+            .iter()
+            .enumerate()
+            .map(|(column, cell)| {
+                if column == last {
+                    cell.clone()
+                } else {
+                    format!("{:>width$}", cell, width = widths[column])
+                }
+            })
+            .collect();
+        out.push_str(cells.join("  ").trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::LEADERBOARD_JSON;
+
+    #[test]
+    fn parses_members_and_stars() {
+        let leaderboard = Leaderboard::from_json(LEADERBOARD_JSON).unwrap();
+        assert_eq!(leaderboard.year, 2024);
+        let names: Vec<String> = leaderboard
+            .members
+            .iter()
+            .map(Member::display_name)
+            .collect();
+        assert_eq!(names, ["Ada", "(anonymous user #2)", "Idle"]);
+
+        let ada = &leaderboard.members[0];
+        assert_eq!(ada.days[&1].delta(), Some(Duration::from_secs(300)));
+        assert_eq!(ada.days[&2].part2, None);
+        assert_eq!(leaderboard.members[2].last_star, None);
+
+        let day1 = PuzzleId::new(2024, 1).unwrap();
+        assert_eq!(
+            ada.days[&1].part1.earned.since_unlock(day1),
+            Some(Duration::from_secs(300))
+        );
+    }
+
+    #[test]
+    fn renders_a_day_with_deltas() {
+        let leaderboard = Leaderboard::from_json(LEADERBOARD_JSON).unwrap();
+        assert_eq!(
+            leaderboard.render_day(1),
+            concat!(
+                " #    Part 1    Part 2      Delta  Name\n",
+                "1)  00:05:00  00:10:00  +00:05:00  Ada\n",
+                "2)  00:01:00  01:03:20  +01:02:20  (anonymous user #2)\n",
+            )
+        );
+        assert_eq!(
+            leaderboard.render_day(2),
+            concat!(
+                " #    Part 1  Part 2  Delta  Name\n",
+                "1)  01:00:00       -      -  Ada\n",
+            )
+        );
+    }
+
+    #[test]
+    fn renders_an_overview() {
+        let leaderboard = Leaderboard::from_json(LEADERBOARD_JSON).unwrap();
+        assert_eq!(
+            leaderboard.render_overview(),
+            concat!(
+                " #  Score  Stars   1   2  Name\n",
+                "1)      5      3   *   .  Ada\n",
+                "2)      3      2   *      (anonymous user #2)\n",
+                "3)      0      0          Idle\n",
+            )
+        );
+    }
+
+    #[test]
+    fn rejects_non_leaderboard_json() {
+        assert!(Leaderboard::from_json("<html>Log in</html>").is_err());
+        assert!(Leaderboard::from_json(r#"{"event": "x", "owner_id": 1, "members": {}}"#).is_err());
+    }
+}
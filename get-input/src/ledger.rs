@@ -0,0 +1,293 @@
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::puzzle::Part;
+use crate::submit::Verdict;
+
+/// One submitted answer and the verdict it received.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub part: Part,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+/// Why an answer was not sent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadyRejected(Verdict),
+    AlreadyCorrect(String),
+    NotBelow(i128),
+    NotAbove(i128),
+    /// The answer has a tab or line break, which the ledger file cannot hold.
+    Unrecordable,
+}
+
+impl fmt::Display for Refusal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Refusal::AlreadyRejected(verdict) => {
+                write!(
+                    f,
+                    "this answer was already submitted and judged {}",
+                    verdict
+                )
+            }
+            Refusal::AlreadyCorrect(answer) => write!(f, "already solved with {}", answer),
+            Refusal::NotBelow(bound) => write!(f, "{} was already too high", bound),
+            Refusal::NotAbove(bound) => write!(f, "{} was already too low", bound),
+            Refusal::Unrecordable => write!(f, "answers cannot contain tabs or line breaks"),
+        }
+    }
+}
+
+/// Append-only record of the answers submitted for one puzzle, one
+/// `level<TAB>verdict<TAB>answer` line per submission.
+#[derive(Debug, Clone)]
+pub struct Ledger {
+    path: PathBuf,
+    entries: Vec<Entry>,
+}
+
+impl Ledger {
+    /// Loads the ledger at `path`; a missing file is an empty ledger.
+    pub fn load(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(err),
+        };
+
+        let mut entries = Vec::new();
+        for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+            let entry = parse_entry(line).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("malformed ledger line in {}: {:?}", path.display(), line),
+                )
+            })?;
+            entries.push(entry);
+        }
+        Ok(Ledger { path, entries })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// Refuses answers that are already known to be wrong, or that fall outside the
+    /// interval left by earlier "too high" and "too low" verdicts.
+    pub fn check(&self, part: Part, answer: &str) -> Result<(), Refusal> {
+        let answer = answer.trim();
+        if !is_recordable(answer) {
+            return Err(Refusal::Unrecordable);
+        }
+        let value: Option<i128> = answer.parse().ok();
+        let mut lowest_too_high: Option<i128> = None;
+        let mut highest_too_low: Option<i128> = None;
+
+        for entry in self.entries.iter().filter(|entry| entry.part == part) {
+            match entry.verdict {
+                Verdict::Correct => return Err(Refusal::AlreadyCorrect(entry.answer.clone())),
+                Verdict::TooHigh | Verdict::TooLow | Verdict::Incorrect
+                    if entry.answer == answer =>
+                {
+                    return Err(Refusal::AlreadyRejected(entry.verdict));
+                }
+                _ => {}
+            }
+
+            if let Ok(previous) = entry.answer.parse::<i128>() {
+                match entry.verdict {
+                    Verdict::TooHigh => {
+                        lowest_too_high =
+                            Some(lowest_too_high.map_or(previous, |b| b.min(previous)))
+                    }
+                    Verdict::TooLow => {
+                        highest_too_low =
+                            Some(highest_too_low.map_or(previous, |b| b.max(previous)))
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        if let Some(value) = value {
+            if let Some(bound) = lowest_too_high.filter(|&bound| value >= bound) {
+                return Err(Refusal::NotBelow(bound));
+            }
+            if let Some(bound) = highest_too_low.filter(|&bound| value <= bound) {
+                return Err(Refusal::NotAbove(bound));
+            }
+        }
+        Ok(())
+    }
+
+    /// Appends a submission to the ledger file.
+    pub fn record(&mut self, part: Part, answer: &str, verdict: Verdict) -> io::Result<()> {
+        if !is_recordable(answer.trim()) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{:?} has a tab or line break", answer),
+            ));
+        }
+        let entry = Entry {
+            part,
+            answer: answer.trim().to_string(),
+            verdict,
+        };
+
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", format_entry(&entry))?;
+
+        self.entries.push(entry);
+        Ok(())
+    }
+}
+
+// Tabs separate the fields and line breaks the entries
+fn is_recordable(answer: &str) -> bool {
+    !answer.contains(['\t', '\n', '\r'])
+}
+
+fn format_entry(entry: &Entry) -> String {
+    let verdict = match entry.verdict {
+        Verdict::Correct => "correct".to_string(),
+        Verdict::TooHigh => "too-high".to_string(),
+        Verdict::TooLow => "too-low".to_string(),
+        Verdict::Incorrect => "incorrect".to_string(),
+        Verdict::AlreadySolved => "already-solved".to_string(),
+        Verdict::RateLimited(wait) => format!("rate-limited:{}", wait.as_secs()),
+    };
+    format!("{}\t{}\t{}", entry.part.level(), verdict, entry.answer)
+}
+
+fn parse_entry(line: &str) -> Option<Entry> {
+    let mut fields = line.splitn(3, '\t');
+    let part = match fields.next()? {
+        "1" => Part::One,
+        "2" => Part::Two,
+        _ => return None,
+    };
+    let verdict = match fields.next()? {
+        "correct" => Verdict::Correct,
+        "too-high" => Verdict::TooHigh,
+        "too-low" => Verdict::TooLow,
+        "incorrect" => Verdict::Incorrect,
+        "already-solved" => Verdict::AlreadySolved,
+        other => {
+            let seconds = other.strip_prefix("rate-limited:")?.parse().ok()?;
+            Verdict::RateLimited(Duration::from_secs(seconds))
+        }
+    };
+    let answer = fields.next()?.to_string();
+    Some(Entry {
+        part,
+        answer,
+        verdict,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ledger_with(entries: &[(Part, &str, Verdict)]) -> (tempfile::TempDir, Ledger) {
+        let dir = tempfile::tempdir().unwrap();
+        let mut ledger = Ledger::load(dir.path().join("answers.tsv")).unwrap();
+        for &(part, answer, verdict) in entries {
+            ledger.record(part, answer, verdict).unwrap();
+        }
+        (dir, ledger)
+    }
+
+    #[test]
+    fn round_trips_through_the_file() {
+        let (_dir, ledger) = ledger_with(&[
+            (Part::One, "42", Verdict::TooLow),
+            (Part::One, "abc def", Verdict::Incorrect),
+            (
+                Part::Two,
+                "7",
+                Verdict::RateLimited(Duration::from_secs(30)),
+            ),
+        ]);
+
+        let reloaded = Ledger::load(ledger.path()).unwrap();
+        assert_eq!(reloaded.entries(), ledger.entries());
+    }
+
+    #[test]
+    fn answers_that_would_corrupt_the_file_are_refused() {
+        let (_dir, mut ledger) = ledger_with(&[]);
+        assert_eq!(
+            ledger.check(Part::One, "12\t34"),
+            Err(Refusal::Unrecordable)
+        );
+        assert_eq!(ledger.check(Part::One, "a\nb"), Err(Refusal::Unrecordable));
+        assert!(ledger
+            .record(Part::One, "a\nb", Verdict::Incorrect)
+            .is_err());
+        ledger.record(Part::One, "42\n", Verdict::TooLow).unwrap();
+
+        let reloaded = Ledger::load(ledger.path()).unwrap();
+        assert_eq!(reloaded.entries(), ledger.entries());
+        assert_eq!(reloaded.entries()[0].answer, "42");
+    }
+
+    #[test]
+    fn refuses_repeated_wrong_answers() {
+        let (_dir, ledger) = ledger_with(&[(Part::One, "abc", Verdict::Incorrect)]);
+        assert_eq!(
+            ledger.check(Part::One, "abc"),
+            Err(Refusal::AlreadyRejected(Verdict::Incorrect))
+        );
+        assert_eq!(ledger.check(Part::Two, "abc"), Ok(()));
+    }
+
+    #[test]
+    fn refuses_answers_outside_known_bounds() {
+        let (_dir, ledger) = ledger_with(&[
+            (Part::One, "100", Verdict::TooHigh),
+            (Part::One, "80", Verdict::TooHigh),
+            (Part::One, "20", Verdict::TooLow),
+        ]);
+        assert_eq!(ledger.check(Part::One, "90"), Err(Refusal::NotBelow(80)));
+        assert_eq!(ledger.check(Part::One, "81"), Err(Refusal::NotBelow(80)));
+        assert_eq!(ledger.check(Part::One, "5"), Err(Refusal::NotAbove(20)));
+        assert_eq!(ledger.check(Part::One, "50"), Ok(()));
+    }
+
+    #[test]
+    fn rate_limited_answers_can_be_retried() {
+        let (_dir, ledger) = ledger_with(&[(
+            Part::Two,
+            "7",
+            Verdict::RateLimited(Duration::from_secs(30)),
+        )]);
+        assert_eq!(ledger.check(Part::Two, "7"), Ok(()));
+    }
+
+    #[test]
+    fn refuses_after_correct_answer() {
+        let (_dir, ledger) = ledger_with(&[(Part::One, "3749", Verdict::Correct)]);
+        assert_eq!(
+            ledger.check(Part::One, "3750"),
+            Err(Refusal::AlreadyCorrect("3749".to_string()))
+        );
+    }
+}
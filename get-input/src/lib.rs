@@ -1,56 +1,157 @@
-use reqwest::Error;
-use std::fs;
-use std::io::{self, Write};
-use std::path::Path;
-
-#[tokio::main]
-pub async fn fetch_input(day: &str) -> Result<String, Error> {
-    //Define the cache file name
-    let cache_file = format!("input_day_{}.txt", day);
-
-    // Check if the cache file exists
-    if Path::new(&cache_file).exists() {
-        // If the cache file exists, read the input from it
-        let input = fs::read_to_string(&cache_file).expect("Failed to read cache file");
-        println!("Using cached input for day {}", day);
-        println!("{}", input);
-        return Ok(input);
-    }
-
-    println!("Fetching input for day {}", day);
-    //Prompt for the session cookie
-    println!("Enter your session cookie:");
-    io::stdout().flush().unwrap();
-    let mut cookie = String::new();
-    io::stdin().read_line(&mut cookie).unwrap();
-    let cookie = cookie.trim();
-    // The URL for the input page
-    let url = format!("https://adventofcode.com/2024/day/{}/input", day);
-    
-    // Create an HTTP client
-    let client = reqwest::Client::new();
-    
-    // Send the GET request with the session cookie
-    let response = client
-        .get(url)
-        .header("Cookie", format!("session={}", cookie))
-        .send()
-        .await?;
-    
-    // Check if the request was successful
-    if response.status().is_success() {
-        // Get the response text
-        let input = response.text().await?;
-        
-        // Print the input or save it to a file
-        println!("Input:\n{}", input);
-        // Save the input to a file
-        fs::write(&cache_file, input.clone()).expect("Failed to write cache file");
-        return Ok(input);
-    } else {
-        // Handle the error
-        println!("Failed to fetch input. Status code: {}", response.status());
-        return Err(response.error_for_status().unwrap_err());
-    }
-    
+mod answer;
+mod cache;
+mod calendar;
+mod client;
+mod crypto;
+mod description;
+mod error;
+mod examples;
+mod html;
+mod input;
+mod leaderboard;
+mod ledger;
+mod logging;
+mod policy;
+mod profile;
+mod puzzle;
+mod recorded;
+mod runtime;
+mod session;
+mod solution;
+mod submit;
+#[cfg(test)]
+mod testing;
+mod transport;
+mod validate;
+
+use runtime::{block_on, join_keyed};
+
+pub use answer::Answer;
+pub use cache::{legacy_inputs, read_legacy_input, Cache, Quarantined, CACHE_DIR_ENV_VAR};
+pub use calendar::Calendar;
+pub use client::{AocClient, OFFLINE_ENV_VAR, WAIT_FOR_UNLOCK_ENV_VAR};
+pub use crypto::{KeySource, KEY_FILE_ENV_VAR, PASSPHRASE_ENV_VAR};
+pub use description::description_to_markdown;
+pub use error::FetchError;
+pub use examples::{check_examples, extract_examples, write_examples, Example};
+pub use input::{PuzzleInput, Shape, ShapeError};
+pub use leaderboard::{DayProgress, Leaderboard, Member, Star, Timestamp, LEADERBOARD_MAX_AGE};
+pub use ledger::{Entry, Ledger, Refusal};
+pub use log::LevelFilter;
+pub use logging::{init_logging, LOG_ENV_VAR};
+pub use policy::{RequestPolicy, DEFAULT_USER_AGENT, USER_AGENT_ENV_VAR};
+pub use profile::{Profile, Profiles};
+pub use puzzle::{
+    format_hms, Part, PuzzleId, PuzzleIdError, BASE_URL, DEFAULT_YEAR, FIRST_YEAR, UNLOCK_HOUR_UTC,
+};
+pub use recorded::{RecordedAnswers, ANSWERS_FILE};
+pub use session::{
+    resolve_session, session_file_path, Session, SessionSource, SessionStatus, SESSION_ENV_VAR,
+};
+pub use solution::{ParseError, Solution};
+pub use submit::{parse_verdict, submit_answer, submit_answer_async, SubmitError, Verdict};
+pub use transport::{HttpTransport, Method, Request, Response, Transport};
+pub use validate::{validate_input, InvalidInput};
+
+/// Fetches the input for a day of the default year, e.g. `fetch_input("7")`. The
+/// returned `PuzzleInput` is normalized and derefs to `str`.
+pub fn fetch_input(day: &str) -> Result<PuzzleInput, FetchError> {
+    fetch_input_with_session(day, None)
+}
+
+/// Like `fetch_input`, but an explicit session token takes precedence over the
+/// `AOC_SESSION` variable, the session file and the prompt.
+pub fn fetch_input_with_session(
+    day: &str,
+    session: Option<&str>,
+) -> Result<PuzzleInput, FetchError> {
+    let id = PuzzleId::parse_day(DEFAULT_YEAR, day)?;
+    let input = block_on(AocClient::new().with_session(session).fetch_input(id))?;
+    Ok(PuzzleInput::from(input))
+}
+
+/// Blocking fetch of any year's puzzle input. Safe to call from inside a tokio runtime,
+/// though async callers should prefer `fetch_input_async`.
+pub fn fetch_input_for(id: PuzzleId) -> Result<PuzzleInput, FetchError> {
+    block_on(fetch_input_async(id))
+}
+
+/// Fetches a puzzle input on the caller's runtime.
+pub async fn fetch_input_async(id: PuzzleId) -> Result<PuzzleInput, FetchError> {
+    AocClient::new()
+        .fetch_input(id)
+        .await
+        .map(PuzzleInput::from)
+}
+
+/// Fetches a puzzle input as a named profile, e.g. a teammate's account, using its
+/// session file and cache namespace.
+pub fn fetch_input_for_profile(id: PuzzleId, profile: &str) -> Result<PuzzleInput, FetchError> {
+    let profile = Profile::new(profile)?;
+    let client = AocClient::new().with_profile(Some(profile));
+    block_on(client.fetch_input(id)).map(PuzzleInput::from)
+}
+
+/// A profile and its input, or why it could not be fetched.
+pub type ProfileInput = (Profile, Result<PuzzleInput, FetchError>);
+
+/// Fetches one puzzle for every profile with a session file, in name order, so a
+/// solution can be checked against several real inputs.
+pub fn fetch_input_for_all_profiles(id: PuzzleId) -> Result<Vec<ProfileInput>, FetchError> {
+    let Some(profiles) = Profiles::from_env() else {
+        return Ok(Vec::new());
+    };
+    let names = profiles
+        .list()
+        .map_err(|err| FetchError::io(profiles.dir(), err))?;
+
+    let fetches = names.into_iter().map(|profile| {
+        let client = AocClient::new().with_profile(Some(profile.clone()));
+        let fetch = async move { client.fetch_input(id).await.map(PuzzleInput::from) };
+        (profile, fetch)
+    });
+    // Profiles::list is already in name order, which join_keyed keeps
+    Ok(block_on(join_keyed(fetches)))
+}
+
+/// Blocking download of every unlocked, uncached day of a year, so later fetches
+/// work offline. Returns the days that were downloaded.
+pub fn prefetch_year(year: u16) -> Result<Vec<PuzzleId>, FetchError> {
+    block_on(AocClient::new().with_offline(false).prefetch(year))
+}
+
+/// Reports whether the resolved session token is still accepted, and for whom.
+pub fn validate_session() -> Result<SessionStatus, FetchError> {
+    block_on(validate_session_async())
+}
+
+pub async fn validate_session_async() -> Result<SessionStatus, FetchError> {
+    AocClient::new().validate_session().await
+}
+
+/// Blocking fetch of a puzzle's description as Markdown.
+pub fn fetch_description(id: PuzzleId) -> Result<String, FetchError> {
+    block_on(AocClient::new().fetch_description(id))
+}
+
+/// Blocking fetch of the example inputs and answers from a puzzle's page.
+pub fn fetch_examples(id: PuzzleId) -> Result<Vec<Example>, FetchError> {
+    block_on(AocClient::new().fetch_examples(id))
+}
+
+/// Blocking fetch of a private leaderboard, polite-polling through the cache.
+pub fn fetch_leaderboard(year: u16, leaderboard: u64) -> Result<Leaderboard, FetchError> {
+    block_on(AocClient::new().fetch_leaderboard(year, leaderboard))
+}
+
+/// Blocking fetch of the stars earned on each day of a year.
+pub fn fetch_calendar(year: u16) -> Result<Calendar, FetchError> {
+    block_on(AocClient::new().fetch_calendar(year))
+}
+
+/// Fetches several inputs concurrently on the caller's runtime, returned in `PuzzleId` order.
+pub async fn fetch_all_async(ids: &[PuzzleId]) -> Vec<(PuzzleId, Result<PuzzleInput, FetchError>)> {
+    let mut results = join_keyed(ids.iter().map(|&id| (id, fetch_input_async(id)))).await;
+    results.sort_by_key(|(id, _)| *id);
+    results
 }
@@ -0,0 +1,51 @@
+use std::env;
+use std::io::Write;
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+/// Overrides the verbosity a binary picks, e.g. `AOC_LOG=trace` to see input bodies.
+pub const LOG_ENV_VAR: &str = "AOC_LOG";
+
+// Writes to stderr so stdout stays free for answers. Info lines are plain progress
+// messages; other levels are prefixed with theirs.
+struct StderrLogger;
+
+impl Log for StderrLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let mut stderr = std::io::stderr().lock();
+        let _ = match record.level() {
+            Level::Info => writeln!(stderr, "{}", record.args()),
+            level => writeln!(
+                stderr,
+                "{}: {}",
+                level.as_str().to_ascii_lowercase(),
+                record.args()
+            ),
+        };
+    }
+
+    fn flush(&self) {
+        let _ = std::io::stderr().flush();
+    }
+}
+
+/// Routes this crate's log output to stderr at `default` verbosity, or at the level
+/// named in `AOC_LOG`. Meant to be called once at the start of a binary; later calls
+/// only change the level.
+pub fn init_logging(default: LevelFilter) {
+    let level = env::var(LOG_ENV_VAR)
+        .ok()
+        .and_then(|value| value.trim().parse().ok())
+        .unwrap_or(default);
+
+    static LOGGER: StderrLogger = StderrLogger;
+    let _ = log::set_logger(&LOGGER);
+    log::set_max_level(level);
+}
@@ -0,0 +1,136 @@
+use std::fs::{self, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Set to override the User-Agent sent with every request, e.g.
+/// `my-aoc-tools (me@example.com)`.
+pub const USER_AGENT_ENV_VAR: &str = "AOC_USER_AGENT";
+
+/// Identifies the tool as the AoC maintainers ask; set `AOC_USER_AGENT` to add a contact.
+pub const DEFAULT_USER_AGENT: &str = concat!(
+    "get-input/",
+    env!("CARGO_PKG_VERSION"),
+    " (Advent of Code input helper; set AOC_USER_AGENT to add a contact address)"
+);
+
+/// How politely requests are sent: a minimum gap between any two requests, shared
+/// by every process using the same cache, and bounded exponential backoff for
+/// server errors and failed connections. 4xx responses are never retried.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestPolicy {
+    pub min_interval: Duration,
+    pub max_retries: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for RequestPolicy {
+    fn default() -> Self {
+        RequestPolicy {
+            min_interval: Duration::from_secs(5),
+            max_retries: 3,
+            initial_backoff: Duration::from_secs(2),
+            max_backoff: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RequestPolicy {
+    /// The wait before retry number `attempt + 1`: doubling from `initial_backoff`
+    /// and capped at `max_backoff`.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt);
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+const CLOCK_SKEW_LIMIT: Duration = Duration::from_secs(60 * 60);
+
+/// Claims the next request slot recorded in `path` and returns how long to wait for
+/// it. Slots are spaced `min_interval` apart; the file is locked while it is updated
+/// so concurrent processes each get their own slot.
+pub(crate) fn reserve_slot(
+    path: &Path,
+    min_interval: Duration,
+    now: SystemTime,
+) -> io::Result<Duration> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)?;
+    file.lock()?;
+
+    let mut contents = String::new();
+    file.read_to_string(&mut contents)?;
+    let previous = contents
+        .trim()
+        .parse()
+        .ok()
+        .map(|millis| UNIX_EPOCH + Duration::from_millis(millis));
+
+    // Queued slots sit a few intervals ahead at most; one an hour out means the
+    // clock moved back, so start over rather than stall
+    let slot = match previous {
+        Some(previous) if previous <= now + CLOCK_SKEW_LIMIT => (previous + min_interval).max(now),
+        _ => now,
+    };
+    let millis = slot
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    file.set_len(0)?;
+    file.seek(SeekFrom::Start(0))?;
+    write!(file, "{}", millis)?;
+
+    Ok(slot.duration_since(now).unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        let policy = RequestPolicy::default();
+        let waits: Vec<u64> = (0..6).map(|n| policy.backoff(n).as_secs()).collect();
+        assert_eq!(waits, [2, 4, 8, 16, 30, 30]);
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_secs(30));
+    }
+
+    #[test]
+    fn slots_are_spaced_by_the_interval() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("last-request");
+        let interval = Duration::from_secs(5);
+        let now = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+
+        assert_eq!(reserve_slot(&path, interval, now).unwrap(), Duration::ZERO);
+        assert_eq!(reserve_slot(&path, interval, now).unwrap(), interval);
+        assert_eq!(reserve_slot(&path, interval, now).unwrap(), interval * 2);
+
+        let later = now + Duration::from_secs(60);
+        assert_eq!(
+            reserve_slot(&path, interval, later).unwrap(),
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn a_clock_moved_back_does_not_stall_requests() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("last-request");
+        let interval = Duration::from_secs(5);
+        let now = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+
+        reserve_slot(&path, interval, now + Duration::from_secs(2 * 3600)).unwrap();
+        assert_eq!(reserve_slot(&path, interval, now).unwrap(), Duration::ZERO);
+    }
+}
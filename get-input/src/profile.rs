@@ -0,0 +1,159 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::error::FetchError;
+use crate::session::{aoc_config_dir, Session, SessionSource};
+
+/// A named account, e.g. a teammate's, with its own session token and cache namespace.
+/// Names are letters, digits, `-` and `_`, so they are safe as file names.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Profile(String);
+
+impl Profile {
+    pub fn new(name: &str) -> Result<Self, FetchError> {
+        let valid = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if valid {
+            Ok(Profile(name.to_string()))
+        } else {
+            Err(FetchError::InvalidProfile(name.to_string()))
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// The directory of profile session files, one file per profile named after it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Profiles {
+    dir: PathBuf,
+}
+
+impl Profiles {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Profiles { dir: dir.into() }
+    }
+
+    /// `$XDG_CONFIG_HOME/aoc/profiles`, falling back to `~/.config/aoc/profiles`.
+    pub fn from_env() -> Option<Self> {
+        aoc_config_dir().map(|dir| Profiles::new(dir.join("profiles")))
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn session_path(&self, profile: &Profile) -> PathBuf {
+        self.dir.join(profile.name())
+    }
+
+    /// Every profile with a session file, in name order.
+    pub fn list(&self) -> io::Result<Vec<Profile>> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err),
+        };
+        let mut profiles = Vec::new();
+        for entry in entries {
+            let entry = entry?;
+            if !entry.file_type()?.is_file() {
+                continue;
+            }
+            if let Some(profile) = entry
+                .file_name()
+                .to_str()
+                .and_then(|n| Profile::new(n).ok())
+            {
+                profiles.push(profile);
+            }
+        }
+        profiles.sort();
+        Ok(profiles)
+    }
+
+    /// Reads a profile's token. Profiles never fall back to `AOC_SESSION` or the
+    /// prompt, which would silently fetch someone else's input.
+    pub fn session(&self, profile: &Profile) -> Result<Session, FetchError> {
+        let path = self.session_path(profile);
+        let missing = || FetchError::MissingProfile {
+            profile: profile.clone(),
+            session_file: Some(path.clone()),
+        };
+        match fs::read_to_string(&path) {
+            Ok(token) if !token.trim().is_empty() => {
+                Ok(Session::new(&token, SessionSource::ConfigFile(path)))
+            }
+            Ok(_) => Err(missing()),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Err(missing()),
+            Err(err) => Err(FetchError::io(&path, err)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_must_be_safe_file_names() {
+        assert_eq!(Profile::new("alice_2").unwrap().name(), "alice_2");
+        for bad in ["", "..", "a/b", "bob smith"] {
+            assert!(
+                matches!(Profile::new(bad), Err(FetchError::InvalidProfile(_))),
+                "{:?}",
+                bad
+            );
+        }
+    }
+
+    #[test]
+    fn lists_profiles_and_reads_their_sessions() {
+        let dir = tempfile::tempdir().unwrap();
+        let profiles = Profiles::new(dir.path());
+        assert!(profiles.list().unwrap().is_empty());
+
+        fs::write(dir.path().join("bob"), "bob-token\n").unwrap();
+        fs::write(dir.path().join("alice"), "alice-token").unwrap();
+        fs::write(dir.path().join("not a profile"), "ignored").unwrap();
+        fs::write(dir.path().join("empty"), "\n").unwrap();
+
+        let names: Vec<String> = profiles
+            .list()
+            .unwrap()
+            .iter()
+            .map(|p| p.to_string())
+            .collect();
+        assert_eq!(names, ["alice", "bob", "empty"]);
+
+        let bob = profiles.session(&Profile::new("bob").unwrap()).unwrap();
+        assert_eq!(bob.token(), "bob-token");
+        assert_eq!(
+            bob.source(),
+            &SessionSource::ConfigFile(dir.path().join("bob"))
+        );
+
+        for missing in ["empty", "carol"] {
+            let err = profiles
+                .session(&Profile::new(missing).unwrap())
+                .unwrap_err();
+            assert!(
+                matches!(err, FetchError::MissingProfile { .. }),
+                "{:?}",
+                err
+            );
+        }
+    }
+}
@@ -0,0 +1,197 @@
+use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The first Advent of Code event.
+pub const FIRST_YEAR: u16 = 2015;
+/// The year `fetch_input` targets when only a day is given.
+pub const DEFAULT_YEAR: u16 = 2024;
+/// Puzzles unlock at midnight UTC-5, which is 05:00 UTC.
+pub const UNLOCK_HOUR_UTC: u64 = 5;
+/// Where the Advent of Code site lives.
+pub const BASE_URL: &str = "https://adventofcode.com";
+
+/// A single puzzle, addressed by event year and day (1-25).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PuzzleId {
+    year: u16,
+    day: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PuzzleIdError {
+    InvalidYear(u16),
+    InvalidDay(u8),
+    Unparsable(String),
+}
+
+impl fmt::Display for PuzzleIdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PuzzleIdError::InvalidYear(year) => {
+                write!(f, "invalid year {}: events start in {}", year, FIRST_YEAR)
+            }
+            PuzzleIdError::InvalidDay(day) => write!(f, "invalid day {}: must be 1-25", day),
+            PuzzleIdError::Unparsable(day) => write!(f, "could not parse day {:?}", day),
+        }
+    }
+}
+
+impl std::error::Error for PuzzleIdError {}
+
+impl PuzzleId {
+    pub fn new(year: u16, day: u8) -> Result<Self, PuzzleIdError> {
+        if year < FIRST_YEAR {
+            return Err(PuzzleIdError::InvalidYear(year));
+        }
+        if !(1..=25).contains(&day) {
+            return Err(PuzzleIdError::InvalidDay(day));
+        }
+        Ok(PuzzleId { year, day })
+    }
+
+    /// Parses a day such as `"7"` or `"07"` for the given year.
+    pub fn parse_day(year: u16, day: &str) -> Result<Self, PuzzleIdError> {
+        let day: u8 = day
+            .trim()
+            .parse()
+            .map_err(|_| PuzzleIdError::Unparsable(day.to_string()))?;
+        PuzzleId::new(year, day)
+    }
+
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    /// The site-relative path of the puzzle page, e.g. `/2024/day/7`.
+    pub fn path(&self) -> String {
+        format!("/{}/day/{}", self.year, self.day)
+    }
+
+    pub fn input_url(&self) -> String {
+        format!("{}{}/input", BASE_URL, self.path())
+    }
+
+    /// The instant the puzzle unlocks: midnight UTC-5 on December `day`.
+    pub fn unlock_time(&self) -> SystemTime {
+        let days = days_from_civil(self.year as i64, 12, self.day as i64);
+        let seconds = days as u64 * 86_400 + UNLOCK_HOUR_UTC * 3600;
+        UNIX_EPOCH + Duration::from_secs(seconds)
+    }
+
+    /// How long until the puzzle unlocks, or `None` if it already has.
+    pub fn time_until_unlock(&self, now: SystemTime) -> Option<Duration> {
+        self.unlock_time()
+            .duration_since(now)
+            .ok()
+            .filter(|remaining| !remaining.is_zero())
+    }
+
+    /// The cache file name, keyed by year and day.
+    pub fn cache_file_name(&self) -> String {
+        format!("input_{}_day_{}.txt", self.year, self.day)
+    }
+}
+
+impl fmt::Display for PuzzleId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} day {}", self.year, self.day)
+    }
+}
+
+/// Formats a duration as `HH:MM:SS`, with hours allowed to exceed 24.
+pub fn format_hms(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+// Days from 1970-01-01 to a proleptic Gregorian date (Howard Hinnant's algorithm)
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month_index = (month + 9) % 12;
+    let day_of_year = (153 * month_index + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// One of the two halves of a puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    /// The `level` form value the answer endpoint expects.
+    pub fn level(&self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "part {}", self.level())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_year_and_day() {
+        assert!(PuzzleId::new(2015, 1).is_ok());
+        assert!(PuzzleId::new(2024, 25).is_ok());
+        assert_eq!(PuzzleId::new(2014, 1), Err(PuzzleIdError::InvalidYear(2014)));
+        assert_eq!(PuzzleId::new(2024, 0), Err(PuzzleIdError::InvalidDay(0)));
+        assert_eq!(PuzzleId::new(2024, 26), Err(PuzzleIdError::InvalidDay(26)));
+    }
+
+    #[test]
+    fn parses_day_strings() {
+        assert_eq!(PuzzleId::parse_day(2024, "07"), PuzzleId::new(2024, 7));
+        assert_eq!(
+            PuzzleId::parse_day(2024, "seven"),
+            Err(PuzzleIdError::Unparsable("seven".to_string()))
+        );
+    }
+
+    #[test]
+    fn unlocks_at_midnight_eastern() {
+        // 2024-12-01T05:00:00Z
+        let id = PuzzleId::new(2024, 1).unwrap();
+        assert_eq!(
+            id.unlock_time(),
+            UNIX_EPOCH + Duration::from_secs(1_733_029_200)
+        );
+
+        let before = id.unlock_time() - Duration::from_secs(3723);
+        assert_eq!(
+            id.time_until_unlock(before),
+            Some(Duration::from_secs(3723))
+        );
+        assert_eq!(id.time_until_unlock(id.unlock_time()), None);
+        assert_eq!(format_hms(Duration::from_secs(3723)), "01:02:03");
+        assert_eq!(format_hms(Duration::from_secs(90_000)), "25:00:00");
+    }
+
+    #[test]
+    fn urls_and_cache_names_include_year() {
+        let id = PuzzleId::new(2019, 3).unwrap();
+        assert_eq!(id.input_url(), "https://adventofcode.com/2019/day/3/input");
+        assert_eq!(id.cache_file_name(), "input_2019_day_3.txt");
+    }
+}
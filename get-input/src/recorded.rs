@@ -0,0 +1,179 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::answer::Answer;
+use crate::ledger::Ledger;
+use crate::puzzle::Part;
+use crate::solution::ParseError;
+use crate::submit::Verdict;
+
+/// Name of the file in a day's directory that records its accepted answers.
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+/// The answers accepted for a day's real input, so a refactor that changes them is
+/// caught. Stored as a small subset of TOML:
+///
+/// ```toml
+/// # Accepted answers for the real input
+/// part1 = 4185
+/// part2 = "LGHZKRCT"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RecordedAnswers {
+    part1: Option<Answer>,
+    part2: Option<Answer>,
+}
+
+impl RecordedAnswers {
+    /// Reads `path`, or returns `None` if nothing has been recorded yet.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Option<RecordedAnswers>> {
+        let path = path.as_ref();
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err),
+        };
+        RecordedAnswers::parse(&text).map(Some).map_err(|err| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}: {}", path.display(), err),
+            )
+        })
+    }
+
+    pub fn parse(text: &str) -> Result<RecordedAnswers, ParseError> {
+        let mut recorded = RecordedAnswers::default();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let at_line = |message: String| ParseError::new(message).at_line(index + 1);
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| at_line(format!("expected `part1 = ...`, found {:?}", line)))?;
+            let part = match key.trim() {
+                "part1" => Part::One,
+                "part2" => Part::Two,
+                other => return Err(at_line(format!("unknown key {:?}", other))),
+            };
+            let value = value.trim();
+            let answer = match value.strip_prefix('"') {
+                Some(quoted) => match quoted.strip_suffix('"') {
+                    Some(text) if !text.contains(['"', '\\']) => Answer::from(text),
+                    _ => return Err(at_line(format!("malformed string {}", value))),
+                },
+                None => match Answer::parse(value) {
+                    Answer::Text(_) => {
+                        return Err(at_line(format!(
+                            "{} is not an integer; quote text answers",
+                            value
+                        )))
+                    }
+                    number => number,
+                },
+            };
+            if recorded.expected(part).is_some() {
+                return Err(at_line(format!("{} is recorded twice", key.trim())));
+            }
+            recorded.record(part, answer);
+        }
+        Ok(recorded)
+    }
+
+    /// The answers the ledger shows were judged correct.
+    pub fn from_ledger(ledger: &Ledger) -> RecordedAnswers {
+        let mut recorded = RecordedAnswers::default();
+        for entry in ledger.entries() {
+            if entry.verdict == Verdict::Correct {
+                recorded.record(entry.part, Answer::parse(&entry.answer));
+            }
+        }
+        recorded
+    }
+
+    pub fn expected(&self, part: Part) -> Option<&Answer> {
+        match part {
+            Part::One => self.part1.as_ref(),
+            Part::Two => self.part2.as_ref(),
+        }
+    }
+
+    pub fn record(&mut self, part: Part, answer: Answer) {
+        match part {
+            Part::One => self.part1 = Some(answer),
+            Part::Two => self.part2 = Some(answer),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.part1.is_none() && self.part2.is_none()
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut text = String::from("# Accepted answers for the real input\n");
+        for part in [Part::One, Part::Two] {
+            let value = match self.expected(part) {
+                None => continue,
+                Some(Answer::Text(text)) if text.contains(['"', '\\']) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("cannot record {:?}: it contains a quote or backslash", text),
+                    ))
+                }
+                Some(Answer::Text(text)) => format!("\"{}\"", text),
+                Some(number) => number.to_string(),
+            };
+            text.push_str(&format!("part{} = {}\n", part.level(), value));
+        }
+        fs::write(path, text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recorded_answers_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(ANSWERS_FILE);
+        assert_eq!(RecordedAnswers::load(&path).unwrap(), None);
+
+        let mut recorded = RecordedAnswers::default();
+        recorded.record(Part::One, Answer::from(5030892084481i64));
+        recorded.record(Part::Two, Answer::from("LGHZKRCT"));
+        recorded.save(&path).unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "# Accepted answers for the real input\npart1 = 5030892084481\npart2 = \"LGHZKRCT\"\n"
+        );
+        assert_eq!(RecordedAnswers::load(&path).unwrap(), Some(recorded));
+    }
+
+    #[test]
+    fn malformed_files_say_which_line() {
+        let err = RecordedAnswers::parse("part1 = 1\npart3 = 2\n").unwrap_err();
+        assert_eq!(err.to_string(), "line 2: unknown key \"part3\"");
+        let err = RecordedAnswers::parse("part1 = abc").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1: abc is not an integer; quote text answers"
+        );
+        assert!(RecordedAnswers::parse("part1 = 1\npart1 = 2").is_err());
+    }
+
+    #[test]
+    fn only_correct_submissions_are_recorded() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut ledger = Ledger::load(dir.path().join("answers.tsv")).unwrap();
+        ledger.record(Part::One, "12", Verdict::TooLow).unwrap();
+        ledger.record(Part::One, "4185", Verdict::Correct).unwrap();
+
+        let recorded = RecordedAnswers::from_ledger(&ledger);
+        assert_eq!(recorded.expected(Part::One), Some(&Answer::from(4185)));
+        assert_eq!(recorded.expected(Part::Two), None);
+    }
+}
@@ -0,0 +1,87 @@
+use std::future::Future;
+use std::panic;
+use std::thread;
+
+use tokio::runtime::{Builder, Handle};
+
+use crate::error::FetchError;
+
+/// Drives a future to completion from synchronous code on a fresh current-thread runtime.
+/// Inside an existing runtime the future runs on a scoped helper thread instead, since
+/// blocking a runtime thread on another runtime panics.
+pub(crate) fn block_on<F>(future: F) -> F::Output
+where
+    F: Future + Send,
+    F::Output: Send,
+{
+    let run = move || {
+        Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("Failed to start tokio runtime")
+            .block_on(future)
+    };
+
+    if Handle::try_current().is_err() {
+        return run();
+    }
+    thread::scope(|scope| match scope.spawn(run).join() {
+        Ok(output) => output,
+        Err(payload) => panic::resume_unwind(payload),
+    })
+}
+
+/// Runs each future as its own task on the current runtime and returns the outputs
+/// with their keys, in the order given. A task that panics becomes a
+/// `FetchError::TaskFailed` for its key instead of taking the caller down with it.
+pub(crate) async fn join_keyed<K, T, F>(
+    futures: impl IntoIterator<Item = (K, F)>,
+) -> Vec<(K, Result<T, FetchError>)>
+where
+    F: Future<Output = Result<T, FetchError>> + Send + 'static,
+    T: Send + 'static,
+{
+    let handles: Vec<_> = futures
+        .into_iter()
+        .map(|(key, future)| (key, tokio::spawn(future)))
+        .collect();
+
+    let mut results = Vec::with_capacity(handles.len());
+    for (key, handle) in handles {
+        let output = handle
+            .await
+            .unwrap_or_else(|err| Err(FetchError::TaskFailed(err)));
+        results.push((key, output));
+    }
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runs_without_a_runtime() {
+        assert_eq!(block_on(async { 1 + 1 }), 2);
+    }
+
+    #[tokio::test]
+    async fn runs_inside_a_runtime() {
+        assert_eq!(block_on(async { 2 + 2 }), 4);
+    }
+
+    #[tokio::test]
+    async fn a_panicking_task_fails_only_its_own_key() {
+        let results = join_keyed([1, 2, 3].map(|key| {
+            (key, async move {
+                assert_ne!(key, 2, "task for key 2 blew up");
+                Ok(key * 10)
+            })
+        }))
+        .await;
+
+        assert!(matches!(results[0], (1, Ok(10))));
+        assert!(matches!(&results[1], (2, Err(FetchError::TaskFailed(_)))));
+        assert!(matches!(results[2], (3, Ok(30))));
+    }
+}
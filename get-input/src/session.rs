@@ -0,0 +1,257 @@
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use tokio::sync::OnceCell;
+
+use crate::error::FetchError;
+use crate::html;
+
+/// Environment variable consulted for the session token.
+pub const SESSION_ENV_VAR: &str = "AOC_SESSION";
+
+/// Where a session token was found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SessionSource {
+    Argument,
+    Environment,
+    ConfigFile(PathBuf),
+    Prompt,
+}
+
+impl fmt::Display for SessionSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SessionSource::Argument => write!(f, "explicit argument"),
+            SessionSource::Environment => write!(f, "{} environment variable", SESSION_ENV_VAR),
+            SessionSource::ConfigFile(path) => write!(f, "config file {}", path.display()),
+            SessionSource::Prompt => write!(f, "interactive prompt"),
+        }
+    }
+}
+
+/// What the site made of a session token, from `validate_session`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SessionStatus {
+    Valid { user: String, source: SessionSource },
+    Expired { source: SessionSource },
+}
+
+impl fmt::Display for SessionStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SessionStatus::Valid { user, source } => {
+                write!(f, "logged in as {} (token from {})", user, source)
+            }
+            SessionStatus::Expired { source } => {
+                write!(f, "expired (token from {})", source)
+            }
+        }
+    }
+}
+
+/// An Advent of Code session cookie together with the source it was resolved from.
+/// The token itself is never printed; `Debug` redacts it.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Session {
+    token: String,
+    source: SessionSource,
+}
+
+impl Session {
+    pub fn new(token: &str, source: SessionSource) -> Self {
+        Session {
+            token: token.trim().to_string(),
+            source,
+        }
+    }
+
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    pub fn source(&self) -> &SessionSource {
+        &self.source
+    }
+}
+
+impl fmt::Debug for Session {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Session")
+            .field("token", &"<redacted>")
+            .field("source", &self.source)
+            .finish()
+    }
+}
+
+/// The session file location: `$XDG_CONFIG_HOME/aoc/session`, falling back to `~/.config/aoc/session`.
+pub fn session_file_path() -> Option<PathBuf> {
+    aoc_config_dir().map(|dir| dir.join("session"))
+}
+
+pub(crate) fn aoc_config_dir() -> Option<PathBuf> {
+    let config_dir = match env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(env::var_os("HOME")?).join(".config"),
+    };
+    Some(config_dir.join("aoc"))
+}
+
+/// Resolves the session token from, in order: the explicit argument, the `AOC_SESSION`
+/// environment variable, the session file and finally an interactive prompt that does not echo.
+pub fn resolve_session(explicit: Option<&str>) -> Result<Session, FetchError> {
+    resolve_from(
+        explicit,
+        env::var(SESSION_ENV_VAR).ok(),
+        session_file_path(),
+        || rpassword::prompt_password("Enter your session cookie: "),
+    )
+}
+
+// Resolved once per process so concurrent fetches do not each prompt for the token
+static SHARED_SESSION: OnceCell<Session> = OnceCell::const_new();
+
+/// Async counterpart of `resolve_session`. Without an explicit token the result is shared
+/// by every fetch in the process, and a prompt runs off the async worker threads.
+pub(crate) async fn shared_session(explicit: Option<&str>) -> Result<Session, FetchError> {
+    if let Some(token) = non_empty(explicit) {
+        return Ok(Session::new(token, SessionSource::Argument));
+    }
+
+    SHARED_SESSION
+        .get_or_try_init(|| async {
+            tokio::task::spawn_blocking(|| resolve_session(None))
+                .await
+                .map_err(FetchError::TaskFailed)?
+        })
+        .await
+        .cloned()
+}
+
+// Every page shows the logged-in user as `<div class="user">Name <span ...>`
+pub(crate) fn logged_in_user(page: &str) -> Option<String> {
+    let nodes = html::parse(page);
+    let user = html::find_all(&nodes, &|e| e.name == "div" && e.has_class("user"));
+    let name = user.first()?.children.iter().find_map(|node| match node {
+        html::Node::Text(text) if !text.trim().is_empty() => Some(text.trim().to_string()),
+        _ => None,
+    })?;
+    Some(name)
+}
+
+fn resolve_from<F>(
+    explicit: Option<&str>,
+    env_value: Option<String>,
+    config_file: Option<PathBuf>,
+    prompt: F,
+) -> Result<Session, FetchError>
+where
+    F: FnOnce() -> io::Result<String>,
+{
+    if let Some(token) = non_empty(explicit) {
+        return Ok(Session::new(token, SessionSource::Argument));
+    }
+
+    if let Some(token) = non_empty(env_value.as_deref()) {
+        return Ok(Session::new(token, SessionSource::Environment));
+    }
+
+    if let Some(path) = &config_file {
+        match fs::read_to_string(path) {
+            Ok(contents) => {
+                if let Some(token) = non_empty(Some(&contents)) {
+                    return Ok(Session::new(token, SessionSource::ConfigFile(path.clone())));
+                }
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => return Err(FetchError::io(path, err)),
+        }
+    }
+
+    // A prompt that cannot be shown (no terminal) or is left blank means there is no token
+    match prompt() {
+        Ok(token) if !token.trim().is_empty() => Ok(Session::new(&token, SessionSource::Prompt)),
+        _ => Err(FetchError::MissingSession {
+            session_file: config_file,
+        }),
+    }
+}
+
+fn non_empty(value: Option<&str>) -> Option<&str> {
+    value.map(str::trim).filter(|token| !token.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_the_user_name_from_the_header() {
+        let page = r#"<header><div><h1 class="title-global"><a href="/">Advent of Code</a></h1><div class="user">Ada Lovelace <span class="star-count">42*</span></div></div></header>"#;
+        assert_eq!(logged_in_user(page).as_deref(), Some("Ada Lovelace"));
+        assert_eq!(
+            logged_in_user(r#"<div class="user">(anonymous user #123)</div>"#).as_deref(),
+            Some("(anonymous user #123)")
+        );
+        assert_eq!(
+            logged_in_user("<header><a href=\"/auth/login\">[Log In]</a></header>"),
+            None
+        );
+    }
+
+    fn no_prompt() -> io::Result<String> {
+        panic!("prompt should not be reached");
+    }
+
+    #[test]
+    fn explicit_argument_wins() {
+        let session = resolve_from(Some("abc"), Some("env".to_string()), None, no_prompt).unwrap();
+        assert_eq!(session.token(), "abc");
+        assert_eq!(session.source(), &SessionSource::Argument);
+    }
+
+    #[test]
+    fn blank_values_fall_through() {
+        let session = resolve_from(Some("  "), Some(" env\n".to_string()), None, no_prompt).unwrap();
+        assert_eq!(session.token(), "env");
+        assert_eq!(session.source(), &SessionSource::Environment);
+    }
+
+    #[test]
+    fn config_file_before_prompt() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session");
+        fs::write(&path, "from-file\n").unwrap();
+
+        let session = resolve_from(None, None, Some(path.clone()), no_prompt).unwrap();
+        assert_eq!(session.token(), "from-file");
+        assert_eq!(session.source(), &SessionSource::ConfigFile(path));
+    }
+
+    #[test]
+    fn prompt_is_last_resort() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = dir.path().join("missing");
+
+        let session = resolve_from(None, None, Some(missing), || Ok("typed\n".to_string())).unwrap();
+        assert_eq!(session.token(), "typed");
+        assert_eq!(session.source(), &SessionSource::Prompt);
+    }
+
+    #[test]
+    fn missing_everywhere_is_an_error() {
+        let result = resolve_from(None, None, None, || Ok("\n".to_string()));
+        assert!(matches!(
+            result,
+            Err(FetchError::MissingSession { session_file: None })
+        ));
+    }
+
+    #[test]
+    fn debug_redacts_token() {
+        let session = Session::new("secret", SessionSource::Argument);
+        assert!(!format!("{:?}", session).contains("secret"));
+    }
+}
@@ -0,0 +1,142 @@
+use std::error::Error;
+use std::fmt;
+use std::num::ParseIntError;
+
+use crate::answer::Answer;
+use crate::input::ShapeError;
+use crate::puzzle::Part;
+
+/// One day's puzzle: the input is parsed once and both parts share the result.
+///
+/// ```ignore
+/// pub struct Day01;
+///
+/// impl Solution for Day01 {
+///     type Parsed = (Vec<i32>, Vec<i32>);
+///
+///     fn parse(input: &str) -> Result<Self::Parsed, ParseError> { ... }
+///     fn part1(lists: &Self::Parsed) -> Answer { ... }
+///     fn part2(lists: &Self::Parsed) -> Answer { ... }
+/// }
+/// ```
+pub trait Solution {
+    type Parsed;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError>;
+    fn part1(parsed: &Self::Parsed) -> Answer;
+    fn part2(parsed: &Self::Parsed) -> Answer;
+
+    /// Parses `input` and solves one part, e.g. for a test against an example.
+    fn solve(input: &str, part: Part) -> Result<Answer, ParseError> {
+        let parsed = Self::parse(input)?;
+        Ok(match part {
+            Part::One => Self::part1(&parsed),
+            Part::Two => Self::part2(&parsed),
+        })
+    }
+}
+
+/// Why an input could not be parsed, with the 1-based line when it is known.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    line: Option<usize>,
+    message: String,
+}
+
+impl ParseError {
+    pub fn new(message: impl Into<String>) -> Self {
+        ParseError {
+            line: None,
+            message: message.into(),
+        }
+    }
+
+    pub fn at_line(mut self, line: usize) -> Self {
+        self.line = Some(line);
+        self
+    }
+
+    pub fn line(&self) -> Option<usize> {
+        self.line
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {}: {}", line, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+impl From<ShapeError> for ParseError {
+    fn from(err: ShapeError) -> Self {
+        ParseError::new(err.to_string())
+    }
+}
+
+impl From<ParseIntError> for ParseError {
+    fn from(err: ParseIntError) -> Self {
+        ParseError::new(format!("invalid number: {}", err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::{PuzzleInput, Shape};
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Parsed = Vec<i64>;
+
+        fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+            let input = PuzzleInput::new(input).expect(Shape::SingleLine)?;
+            input
+                .split(',')
+                .map(|n| n.trim().parse().map_err(ParseError::from))
+                .collect()
+        }
+
+        fn part1(numbers: &Self::Parsed) -> Answer {
+            numbers.iter().sum::<i64>().into()
+        }
+
+        fn part2(numbers: &Self::Parsed) -> Answer {
+            numbers.iter().product::<i64>().into()
+        }
+    }
+
+    #[test]
+    fn solve_parses_and_runs_one_part() {
+        assert_eq!(Sum::solve("1, 2, 3\n", Part::One).unwrap(), Answer::from(6));
+        assert_eq!(Sum::solve("2,3,4", Part::Two).unwrap(), Answer::from(24));
+    }
+
+    #[test]
+    fn parse_errors_say_what_and_where() {
+        let err = Sum::solve("1,x", Part::One).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid number: invalid digit found in string"
+        );
+        assert_eq!(
+            Sum::solve("1\n2", Part::One).unwrap_err().to_string(),
+            "expected a single line, found 2"
+        );
+        assert_eq!(
+            ParseError::new("expected two numbers")
+                .at_line(3)
+                .to_string(),
+            "line 3: expected two numbers"
+        );
+    }
+}
@@ -0,0 +1,259 @@
+use std::fmt;
+use std::io;
+use std::time::Duration;
+
+use crate::client::AocClient;
+use crate::error::FetchError;
+use crate::ledger::Refusal;
+use crate::puzzle::{Part, PuzzleId};
+use crate::runtime::block_on;
+
+/// What the site made of a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Incorrect,
+    AlreadySolved,
+    RateLimited(Duration),
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "incorrect (too high)"),
+            Verdict::TooLow => write!(f, "incorrect (too low)"),
+            Verdict::Incorrect => write!(f, "incorrect"),
+            Verdict::AlreadySolved => write!(f, "already solved"),
+            Verdict::RateLimited(wait) => write!(f, "rate limited, wait {}s", wait.as_secs()),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum SubmitError {
+    Fetch(FetchError),
+    UnrecognizedResponse(String),
+    Refused(Refusal),
+    Io(io::Error),
+}
+
+impl fmt::Display for SubmitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SubmitError::Fetch(err) => write!(f, "{}", err),
+            SubmitError::UnrecognizedResponse(text) => {
+                write!(f, "could not understand the response: {}", text)
+            }
+            SubmitError::Refused(refusal) => write!(f, "not submitted: {}", refusal),
+            SubmitError::Io(err) => write!(f, "could not update the answer ledger: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for SubmitError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SubmitError::Fetch(err) => Some(err),
+            SubmitError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<FetchError> for SubmitError {
+    fn from(err: FetchError) -> Self {
+        SubmitError::Fetch(err)
+    }
+}
+
+impl From<io::Error> for SubmitError {
+    fn from(err: io::Error) -> Self {
+        SubmitError::Io(err)
+    }
+}
+
+/// Submits an answer for one part of a puzzle and reports the verdict.
+/// Answers the ledger already knows to be wrong are refused without a request,
+/// and every verdict is recorded in the ledger.
+pub fn submit_answer(id: PuzzleId, part: Part, answer: &str) -> Result<Verdict, SubmitError> {
+    block_on(submit_answer_async(id, part, answer))
+}
+
+/// Async counterpart of `submit_answer` for callers already on a tokio runtime.
+pub async fn submit_answer_async(
+    id: PuzzleId,
+    part: Part,
+    answer: &str,
+) -> Result<Verdict, SubmitError> {
+    AocClient::new().submit_answer(id, part, answer).await
+}
+
+/// Reads the verdict out of the HTML page the answer endpoint responds with.
+pub fn parse_verdict(html: &str) -> Option<Verdict> {
+    let text = article_text(html);
+
+    if text.contains("That's the right answer") {
+        Some(Verdict::Correct)
+    } else if text.contains("You gave an answer too recently") {
+        Some(Verdict::RateLimited(parse_wait(&text).unwrap_or_default()))
+    } else if text.contains("That's not the right answer") {
+        if text.contains("your answer is too high") {
+            Some(Verdict::TooHigh)
+        } else if text.contains("your answer is too low") {
+            Some(Verdict::TooLow)
+        } else {
+            Some(Verdict::Incorrect)
+        }
+    } else if text.contains("You don't seem to be solving the right level") {
+        Some(Verdict::AlreadySolved)
+    } else {
+        None
+    }
+}
+
+// Parses the "You have 1m 23s left to wait" part of a rate limit message
+fn parse_wait(text: &str) -> Option<Duration> {
+    let start = text.find("You have ")? + "You have ".len();
+    let end = start + text[start..].find(" left to wait")?;
+
+    let mut seconds = 0;
+    for token in text[start..end].split_whitespace() {
+        let (index, unit) = token.char_indices().last()?;
+        let value: u64 = token[..index].parse().ok()?;
+        seconds += match unit {
+            'h' => value * 3600,
+            'm' => value * 60,
+            's' => value,
+            _ => return None,
+        };
+    }
+    Some(Duration::from_secs(seconds))
+}
+
+// The text inside the page's <article>, with tags stripped and whitespace collapsed
+pub(crate) fn article_text(html: &str) -> String {
+    let body = match (html.find("<article"), html.find("</article>")) {
+        (Some(start), Some(end)) if start < end => &html[start..end],
+        _ => html,
+    };
+
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in body.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::Cache;
+    use crate::testing::stub_server;
+    use crate::transport::HttpTransport;
+
+    const USER_AGENT: &str = "submit-test (test@example.com)";
+
+    fn page(article: &str) -> String {
+        format!(
+            "<html><body><main><article><p>{}</p></article></main></body></html>",
+            article
+        )
+    }
+
+    fn client(base_url: &str, cache: &tempfile::TempDir) -> AocClient {
+        AocClient::with_transport(HttpTransport::default().with_user_agent(USER_AGENT))
+            .with_base_url(base_url)
+            .with_cache(Cache::new(cache.path()))
+            .with_session(Some("cookie"))
+            .with_legacy_dir(None)
+    }
+
+    #[test]
+    fn parses_verdicts() {
+        let cases = [
+            ("That's the right answer! You are one gold star closer.", Verdict::Correct),
+            ("That's not the right answer; your answer is too high.", Verdict::TooHigh),
+            ("That's not the right answer; your answer is too low.", Verdict::TooLow),
+            ("That's not the right answer. If you're stuck, make sure...", Verdict::Incorrect),
+            ("You don't seem to be solving the right level. Did you already complete it?", Verdict::AlreadySolved),
+            (
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 1m 23s left to wait.",
+                Verdict::RateLimited(Duration::from_secs(83)),
+            ),
+        ];
+        for (article, verdict) in cases {
+            assert_eq!(parse_verdict(&page(article)), Some(verdict), "{}", article);
+        }
+        assert_eq!(parse_verdict(&page("Something else entirely")), None);
+    }
+
+    #[test]
+    fn odd_wait_times_are_not_parsed() {
+        assert_eq!(
+            parse_wait("You have 2m 5s left to wait."),
+            Some(Duration::from_secs(125))
+        );
+        assert_eq!(parse_wait("You have 1mé left to wait."), None);
+        assert_eq!(parse_wait("You have é left to wait."), None);
+        assert_eq!(parse_wait("You have 5x left to wait."), None);
+    }
+
+    #[tokio::test]
+    async fn submits_form_to_stub_server() {
+        let (base_url, server) = stub_server(200, page("That's the right answer!"));
+        let dir = tempfile::tempdir().unwrap();
+        let id = PuzzleId::new(2024, 7).unwrap();
+
+        let verdict = client(&base_url, &dir)
+            .submit_answer(id, Part::Two, "11387")
+            .await
+            .unwrap();
+        assert_eq!(verdict, Verdict::Correct);
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /2024/day/7/answer HTTP/1.1"));
+        assert!(request.contains("cookie: session=cookie"));
+        assert!(request.contains(&format!("user-agent: {}", USER_AGENT)));
+        assert!(request.ends_with("level=2&answer=11387"));
+    }
+
+    #[tokio::test]
+    async fn reports_unrecognized_pages() {
+        let (base_url, server) = stub_server(200, page("Welcome to Advent of Code"));
+        let dir = tempfile::tempdir().unwrap();
+        let id = PuzzleId::new(2024, 7).unwrap();
+
+        let result = client(&base_url, &dir)
+            .submit_answer(id, Part::One, "1")
+            .await;
+        assert!(
+            matches!(result, Err(SubmitError::UnrecognizedResponse(text)) if text == "Welcome to Advent of Code")
+        );
+        server.join().unwrap();
+    }
+
+    #[tokio::test]
+    async fn reports_http_errors() {
+        let (base_url, server) = stub_server(500, page("Internal error"));
+        let dir = tempfile::tempdir().unwrap();
+        let id = PuzzleId::new(2024, 7).unwrap();
+
+        let result = client(&base_url, &dir)
+            .submit_answer(id, Part::One, "1")
+            .await;
+        assert!(matches!(
+            result,
+            Err(SubmitError::Fetch(FetchError::Http { status, .. })) if status == 500
+        ));
+        server.join().unwrap();
+    }
+}
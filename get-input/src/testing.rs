@@ -0,0 +1,140 @@
+//! Test doubles: an in-process fake `Transport` and a one-shot HTTP stub server.
+
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+use reqwest::StatusCode;
+
+use crate::error::FetchError;
+use crate::transport::{Request, Response, Transport};
+
+/// A real-looking day 1 input.
+pub const INPUT_PAGE: &str = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+
+/// A private leaderboard with a finisher, an anonymous member and an idle one.
+pub const LEADERBOARD_JSON: &str = r#"{
+  "event": "2024",
+  "owner_id": 1,
+  "members": {
+    "1": {"id": 1, "name": "Ada", "local_score": 5, "stars": 3, "global_score": 0,
+          "last_star_ts": 1733119200,
+          "completion_day_level": {
+            "1": {"1": {"get_star_ts": 1733029500, "star_index": 10},
+                  "2": {"get_star_ts": 1733029800, "star_index": 20}},
+            "2": {"1": {"get_star_ts": 1733119200, "star_index": 30}}}},
+    "2": {"id": 2, "name": null, "local_score": 3, "stars": 2, "global_score": 0,
+          "last_star_ts": 1733033000,
+          "completion_day_level": {
+            "1": {"1": {"get_star_ts": 1733029260, "star_index": 5},
+                  "2": {"get_star_ts": 1733033000, "star_index": 40}}}},
+    "3": {"id": 3, "name": "Idle", "local_score": 0, "stars": 0, "global_score": 0,
+          "last_star_ts": 0, "completion_day_level": {}}
+  }
+}"#;
+
+/// A year page with day 1 fully solved, day 2 half solved and day 3 just unlocked.
+pub const CALENDAR_PAGE: &str = r#"<html><body><main>
+<pre class="calendar"><a aria-label="Day 1, two stars" href="/2024/day/1" class="calendar-day1 calendar-verycomplete">  .--'~ ~ ~|        .-' *       \  /     '-.   <span class="calendar-day"> 1</span> <span class="calendar-mark-complete">*</span><span class="calendar-mark-verycomplete">*</span></a>
+<a aria-label="Day 2, one star" href="/2024/day/2" class="calendar-day2 calendar-complete">  '-.~ ~ ~|<span class="calendar-day"> 2</span></a>
+<a aria-label="Day 3" href="/2024/day/3" class="calendar-day3">  ~ ~ ~ ~|<span class="calendar-day"> 3</span></a>
+<span aria-hidden="true" class="calendar-day4">                        <span class="calendar-day"> 4</span></span>
+</pre>
+</main></body></html>"#;
+
+/// Serves canned responses by URL path and records every request it sees.
+/// Queued responses for a path are served in order, the last one repeating;
+/// unrouted paths fail like a refused connection.
+#[derive(Debug, Clone, Default)]
+pub struct FakeTransport {
+    routes: Arc<Mutex<HashMap<String, Vec<Response>>>>,
+    requests: Arc<Mutex<Vec<Request>>>,
+}
+
+impl FakeTransport {
+    pub fn new() -> Self {
+        FakeTransport::default()
+    }
+
+    pub fn route(self, path: &str, status: StatusCode, body: &str) -> Self {
+        self.respond(path, Response::new(status, body))
+    }
+
+    pub fn respond(self, path: &str, response: Response) -> Self {
+        self.routes
+            .lock()
+            .unwrap()
+            .entry(path.to_string())
+            .or_default()
+            .push(response);
+        self
+    }
+
+    pub fn requests(&self) -> Vec<Request> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+impl Transport for FakeTransport {
+    async fn send(&self, request: Request) -> Result<Response, FetchError> {
+        self.requests.lock().unwrap().push(request.clone());
+
+        let path = match request.url.find("://") {
+            Some(scheme) => {
+                let rest = &request.url[scheme + 3..];
+                rest.find('/').map_or("/", |start| &rest[start..])
+            }
+            None => request.url.as_str(),
+        };
+
+        let mut routes = self.routes.lock().unwrap();
+        match routes.get_mut(path) {
+            Some(queue) if queue.len() > 1 => Ok(queue.remove(0)),
+            Some(queue) if !queue.is_empty() => Ok(queue[0].clone()),
+            _ => Err(FetchError::network(
+                &request.url,
+                io::Error::new(io::ErrorKind::ConnectionRefused, "no route in fake"),
+            )),
+        }
+    }
+}
+
+/// Serves a single canned response on a local port and hands back the raw request it received.
+pub fn stub_server(status: u16, body: String) -> (String, JoinHandle<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+    let handle = thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream);
+        let mut request = String::new();
+        let mut content_length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                content_length = value.trim().parse().unwrap();
+            }
+            request.push_str(&line);
+            if line == "\r\n" {
+                break;
+            }
+        }
+        let mut content = vec![0; content_length];
+        reader.read_exact(&mut content).unwrap();
+        request.push_str(&String::from_utf8(content).unwrap());
+
+        let response = format!(
+            "HTTP/1.1 {} OK\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        );
+        reader.get_mut().write_all(response.as_bytes()).unwrap();
+        request
+    });
+
+    (base_url, handle)
+}
@@ -0,0 +1,121 @@
+use std::env;
+use std::future::Future;
+
+use reqwest::header::USER_AGENT;
+use reqwest::StatusCode;
+
+use crate::error::FetchError;
+use crate::policy::{DEFAULT_USER_AGENT, USER_AGENT_ENV_VAR};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    Get,
+    Post,
+}
+
+/// A request to the site, already carrying the session token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    pub method: Method,
+    pub url: String,
+    pub session: String,
+    pub form: Vec<(String, String)>,
+}
+
+impl Request {
+    pub fn get(url: &str, session: &str) -> Self {
+        Request {
+            method: Method::Get,
+            url: url.to_string(),
+            session: session.to_string(),
+            form: Vec::new(),
+        }
+    }
+
+    pub fn post(url: &str, session: &str, form: &[(&str, &str)]) -> Self {
+        Request {
+            method: Method::Post,
+            url: url.to_string(),
+            session: session.to_string(),
+            form: form
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: StatusCode,
+    pub body: String,
+}
+
+impl Response {
+    pub fn new(status: StatusCode, body: &str) -> Self {
+        Response {
+            status,
+            body: body.to_string(),
+        }
+    }
+}
+
+/// Sends requests to the site. `HttpTransport` is the real thing; tests swap in a fake.
+pub trait Transport: Send + Sync {
+    fn send(&self, request: Request) -> impl Future<Output = Result<Response, FetchError>> + Send;
+}
+
+/// `Transport` backed by a `reqwest::Client`. Every request carries a User-Agent
+/// from `AOC_USER_AGENT`, or `DEFAULT_USER_AGENT` when it is unset.
+#[derive(Debug, Clone)]
+pub struct HttpTransport {
+    client: reqwest::Client,
+    user_agent: String,
+}
+
+impl HttpTransport {
+    pub fn new(client: reqwest::Client) -> Self {
+        let user_agent = env::var(USER_AGENT_ENV_VAR)
+            .ok()
+            .filter(|agent| !agent.trim().is_empty())
+            .unwrap_or_else(|| DEFAULT_USER_AGENT.to_string());
+        HttpTransport { client, user_agent }
+    }
+
+    pub fn with_user_agent(mut self, user_agent: &str) -> Self {
+        self.user_agent = user_agent.to_string();
+        self
+    }
+
+    pub fn user_agent(&self) -> &str {
+        &self.user_agent
+    }
+}
+
+impl Default for HttpTransport {
+    fn default() -> Self {
+        HttpTransport::new(reqwest::Client::new())
+    }
+}
+
+impl Transport for HttpTransport {
+    async fn send(&self, request: Request) -> Result<Response, FetchError> {
+        let builder = match request.method {
+            Method::Get => self.client.get(&request.url),
+            Method::Post => self.client.post(&request.url).form(&request.form),
+        };
+
+        let response = builder
+            .header(USER_AGENT, &self.user_agent)
+            .header("Cookie", format!("session={}", request.session))
+            .send()
+            .await
+            .map_err(|err| FetchError::network(&request.url, err))?;
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .map_err(|err| FetchError::network(&request.url, err))?;
+        Ok(Response { status, body })
+    }
+}
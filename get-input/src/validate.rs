@@ -0,0 +1,114 @@
+use std::fmt;
+
+// Notices the site serves in place of an input, sometimes with a success status
+const ERROR_NOTICES: [&str; 4] = [
+    "Puzzle inputs differ by user",
+    "Please log in",
+    "Please don't repeatedly request this endpoint before it unlocks",
+    "Internal Server Error",
+];
+
+/// Why a response body is not a puzzle input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvalidInput {
+    Empty,
+    Html,
+    ErrorNotice(&'static str),
+}
+
+impl fmt::Display for InvalidInput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidInput::Empty => write!(f, "the body is empty"),
+            InvalidInput::Html => write!(f, "the body is an HTML page"),
+            InvalidInput::ErrorNotice(notice) => write!(f, "the body is the {:?} notice", notice),
+        }
+    }
+}
+
+// The page `/auth/login` serves, where an expired session gets redirected
+const LOGIN_PAGE_NOTICE: &str = "identify yourself via one of these services";
+
+/// Whether a body is the login page, i.e. the request was redirected there because
+/// the session is no longer valid.
+pub(crate) fn is_login_page(body: &str) -> bool {
+    body.contains(LOGIN_PAGE_NOTICE)
+}
+
+/// Checks that a body looks like a puzzle input rather than an error page.
+pub fn validate_input(body: &str) -> Result<(), InvalidInput> {
+    let trimmed = body.trim_start();
+    if trimmed.is_empty() {
+        return Err(InvalidInput::Empty);
+    }
+
+    // Inputs may contain '<' (arrows, moves), so only whole documents count as HTML
+    let head: String = trimmed
+        .chars()
+        .take(64)
+        .collect::<String>()
+        .to_ascii_lowercase();
+    let html_starts = ["<!doctype", "<html", "<head", "<body", "<?xml"];
+    if html_starts.iter().any(|start| head.starts_with(start))
+        || body.to_ascii_lowercase().contains("</html>")
+    {
+        return Err(InvalidInput::Html);
+    }
+
+    for notice in ERROR_NOTICES {
+        if body.contains(notice) {
+            return Err(InvalidInput::ErrorNotice(notice));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_real_inputs() {
+        assert_eq!(validate_input("3   4\n4   3\n"), Ok(()));
+        assert_eq!(
+            validate_input("<vv>^<v^>v>^vv^v>v<>v^v<v<^vv<<<^><<><>>\n"),
+            Ok(())
+        );
+        assert_eq!(validate_input("##########\n#..O..O.O#\n"), Ok(()));
+    }
+
+    #[test]
+    fn rejects_empty_bodies() {
+        assert_eq!(validate_input(" \n"), Err(InvalidInput::Empty));
+    }
+
+    #[test]
+    fn rejects_html_pages() {
+        assert_eq!(
+            validate_input("<!DOCTYPE html>\n<html lang=\"en-us\"><head></head></html>"),
+            Err(InvalidInput::Html)
+        );
+        assert_eq!(
+            validate_input("\n<html><body>Oops</body></html>"),
+            Err(InvalidInput::Html)
+        );
+    }
+
+    #[test]
+    fn recognizes_the_login_page() {
+        assert!(is_login_page(
+            "<article><p>To play, please identify yourself via one of these services:</p></article>"
+        ));
+        assert!(!is_login_page("3   4\n"));
+    }
+
+    #[test]
+    fn rejects_error_notices() {
+        assert_eq!(
+            validate_input(
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"
+            ),
+            Err(InvalidInput::ErrorNotice("Puzzle inputs differ by user"))
+        );
+    }
+}
@@ -1,15 +1,23 @@
 #!/bin/bash
-for i in {0..9}; do
-  if [ $i -gt 0 ]; then
-    mkdir -p "day-0$i"
-    cp -a starter/. day-0$i/
-  fi
+# Creates day-NN from starter/, e.g. `./setup.sh 10`. Add the new crate to
+# aoc/Cargo.toml and aoc/src/registry.rs (as day_NN::DayNN) so
+# `aoc run --day N` can find it. Once both parts are accepted,
+# `aoc record --day N` writes the answers.toml that `aoc check` compares against.
+set -e
 
-  mkdir -p "day-1$i"
-  cp -a starter/. day-1$i/
+if [ $# -ne 1 ] || ! [[ $1 =~ ^[0-9]+$ ]] || [ "$1" -lt 1 ] || [ "$1" -gt 25 ]; then
+  echo "Usage: ./setup.sh <day 1-25>" >&2
+  exit 1
+fi
 
-  if [ $i -lt 6 ]; then
-    mkdir -p "day-2$i"
-    cp -a starter/. day-2$i/
-  fi
-done
\ No newline at end of file
+dir=$(printf "day-%02d" "$((10#$1))")
+if [ -e "$dir" ]; then
+  echo "$dir already exists" >&2
+  exit 1
+fi
+
+mkdir -p "$dir"
+cp -a starter/Cargo.toml starter/.gitignore starter/src "$dir"/
+sed -i "s/^name = \"starter\"/name = \"$dir\"/" "$dir/Cargo.toml"
+sed -i "s/DayNN/$(printf "Day%02d" "$((10#$1))")/g" "$dir/src/lib.rs"
+echo "Created $dir"
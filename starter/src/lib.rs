@@ -1,31 +1,22 @@
-pub fn part1(input: &str) -> String {
-  "nothing".to_string()
-}
+use get_input::{Answer, ParseError, Solution};
 
-pub fn part2(input: &str) -> String {
-  "nothing".to_string()
-}
+pub struct DayNN;
 
-#[cfg(test)]
-mod tests {
-  use super::*;
+impl Solution for DayNN {
+  type Parsed = String;
 
-  const INPUT: &str = "vJrwpWtwJgWrhcsFMMfFFhFp
-jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
-PmmdzqPrVvPwwTWBwg
-wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
-ttgJtRGJQctTZtZT
-CrZsJsPPZsGzwwsLwLmpwMDw";
+  fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+    Ok(input.to_string())
+  }
 
-  #[test]
-  fn part1_works() {
-      let result = part1(INPUT);
-      assert_eq!(result, "157");
+  fn part1(_input: &Self::Parsed) -> Answer {
+    "nothing".into()
   }
 
-  #[test]
-  fn part2_works() {
-      let result = part2(INPUT);
-      assert_eq!(result, "70");
+  fn part2(_input: &Self::Parsed) -> Answer {
+    "nothing".into()
   }
-}
\ No newline at end of file
+}
+
+// Checks both parts against the fixtures `aoc-input examples` writes to examples/
+get_input::example_tests!(DayNN);